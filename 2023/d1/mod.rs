@@ -0,0 +1,38 @@
+use common::{Error, ParseError, Solution};
+
+pub mod part1;
+pub mod part2;
+
+/// First and last digit of a line as a two digit number.
+fn calibration_value(line: &str, numbers: &[u32]) -> Result<u32, ParseError>
+{
+    match (numbers.first(), numbers.last()) {
+        (Some(first), Some(last)) => Ok(first * 10 + last),
+        _ => Err(ParseError::new(line, line, "at least one digit")),
+    }
+}
+
+pub struct Day1;
+
+impl Solution for Day1
+{
+    type Input = Vec<String>;
+    type Output = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        Ok(input.lines()
+            .map(|l| l.to_string())
+            .collect())
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        Ok(part1::solve(input)?)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        Ok(part2::solve(input)?)
+    }
+}
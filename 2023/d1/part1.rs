@@ -1,36 +1,20 @@
-use std::env;
-use std::fs;
-use std::io::{self, BufReader, BufRead};
+use common::ParseError;
 
-fn main() -> io::Result<()>
-{
-    if env::args().len() != 2
-    {
-        panic!("Usage: ./main <filename>");
-    }
-
-    let filename = env::args().nth_back(0).unwrap();
-
-    let file = fs::File::open(filename)?;
-
-    let reader = BufReader::new(file);
+use super::calibration_value;
 
+pub fn solve(lines: &[String]) -> Result<u32, ParseError>
+{
     let mut res: u32 = 0;
 
-    for line in reader.lines()
+    for (i, val) in lines.iter().enumerate()
     {
-        if let Ok(val) = line {
-
-            let numbers: Vec<u32> = val.chars()
-                .filter_map(|a| a.to_digit(10))
-                .collect();
-
-            let partial = numbers.first().unwrap() * 10 + numbers.last().unwrap();
-            res += partial;
+        let numbers: Vec<u32> = val.chars()
+            .filter_map(|a| a.to_digit(10))
+            .collect();
 
-        }
+        let partial = calibration_value(val, &numbers).map_err(|e| e.with_line(i + 1))?;
+        res += partial;
     }
-    println!("{res}");
 
-    Ok(())
+    Ok(res)
 }
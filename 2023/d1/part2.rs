@@ -1,7 +1,6 @@
-use std::io::{self, BufReader, BufRead};
-use std::env;
-use std::fs;
-use std::vec::Vec;
+use common::ParseError;
+
+use super::calibration_value;
 
 fn get_characters(line: &str) -> Vec<u32>
 {
@@ -23,32 +22,16 @@ fn get_characters(line: &str) -> Vec<u32>
     res
 }
 
-fn main() -> io::Result<()> 
+pub fn solve(lines: &[String]) -> Result<u32, ParseError>
 {
-
-    if env::args().len() != 2
-    {
-        panic!("Usage: ./main <filename>");
-    }
-
-    let filename = env::args().nth_back(0).unwrap();
-
-    let file = fs::File::open(filename)?;
-
-    let reader = BufReader::new(file);
-
     let mut res: u32 = 0;
 
-    for line in reader.lines()
+    for (i, val) in lines.iter().enumerate()
     {
-        if let Ok(val) = line {
-            let numbers = get_characters(val.as_str());
-            let partial = numbers.first().unwrap() * 10 + numbers.last().unwrap();
-            res += partial;
-            println!("{val} => {numbers:?} => {partial}");
-        }
+        let numbers = get_characters(val.as_str());
+        let partial = calibration_value(val, &numbers).map_err(|e| e.with_line(i + 1))?;
+        res += partial;
     }
-    println!("{res}");
 
-    Ok(())
+    Ok(res)
 }
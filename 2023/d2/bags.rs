@@ -0,0 +1,264 @@
+use common::command::arg;
+use common::input::Source;
+use common::{Error, Solution};
+
+use super::{colours, Day2, Game, GameSet};
+
+/// The smallest bag making every game of `games` possible.
+pub fn minimum<'a>(games: impl IntoIterator<Item = &'a Game>) -> GameSet
+{
+    let mut res = GameSet::default();
+    for game in games
+    {
+        for (colour, &count) in &game.get_max_set().counts
+        {
+            let max = res.counts.entry(colour.clone()).or_insert(0);
+            *max = (*max).max(count);
+        }
+    }
+
+    res
+}
+
+/// `bag` with every colour of `colours` present, zero if unused.
+fn with_colours(bag: &GameSet, colours: &[&str]) -> GameSet
+{
+    let mut res = bag.clone();
+    for colour in colours
+    {
+        res.counts.entry(colour.to_string()).or_insert(0);
+    }
+
+    res
+}
+
+fn total(bag: &GameSet) -> u64
+{
+    bag.counts.values().map(|&c| u64::from(c)).sum()
+}
+
+/// How many bags of exactly `budget` cubes over the colours of `games`
+/// make every game of `chosen` possible.
+pub fn count(games: &[Game], chosen: &[&Game], budget: u32) -> Result<u128, Error>
+{
+    let colours = colours(games).len() as u128;
+    let spare = match u64::from(budget).checked_sub(total(&minimum(chosen.iter().copied()))) {
+        Some(v) => u128::from(v),
+        None => return Ok(0),
+    };
+    if colours == 0
+    {
+        return Ok(u128::from(spare == 0));
+    }
+
+    // The spare cubes spread over the colours: C(spare + colours - 1, colours - 1).
+    let mut res = 1u128;
+    for i in 1..colours
+    {
+        res = res
+            .checked_mul(spare + i)
+            .ok_or_else(|| Error::Solve(format!("more than {} bags", u128::MAX)))?
+            / i;
+    }
+
+    Ok(res)
+}
+
+/// Calls `visit` on every bag `count` counts, in lexicographic order, until
+/// it returns `false`.
+pub fn enumerate<F>(games: &[Game], chosen: &[&Game], budget: u32, mut visit: F)
+where
+    F: FnMut(&GameSet) -> bool,
+{
+    let colours = colours(games).into_iter().collect::<Vec<&str>>();
+    let mut bag = with_colours(&minimum(chosen.iter().copied()), &colours);
+
+    if let Some(spare) = u64::from(budget).checked_sub(total(&bag))
+    {
+        spread(&mut bag, &colours, spare, &mut visit);
+    }
+}
+
+/// Hands out `spare` cubes over `colours`, the last one taking the rest.
+fn spread<F>(bag: &mut GameSet, colours: &[&str], spare: u64, visit: &mut F) -> bool
+where
+    F: FnMut(&GameSet) -> bool,
+{
+    let (colour, rest) = match colours.split_first() {
+        Some(v) => v,
+        // Only reached with spare cubes when there are no colours at all.
+        None if spare > 0 => return true,
+        None => return visit(bag),
+    };
+
+    let base = bag.get(colour);
+    let extra = match rest.is_empty() {
+        true => spare..=spare,
+        false => 0..=spare,
+    };
+
+    for extra in extra
+    {
+        // The budget is a `u32`, so no colour can exceed it.
+        bag.counts.insert(colour.to_string(), base + extra as u32);
+        if !spread(bag, rest, spare - extra, visit)
+        {
+            bag.counts.insert(colour.to_string(), base);
+            return false;
+        }
+    }
+    bag.counts.insert(colour.to_string(), base);
+
+    true
+}
+
+/// Whether `a` holds at least as many cubes as `b` of every colour.
+fn covers(a: &GameSet, b: &GameSet) -> bool
+{
+    a.matches_requirements(b)
+}
+
+/// Picks a count for each colour in turn among the counts the remaining
+/// games need, keeping only the smallest last count that leaves `k` games.
+fn candidates(games: &[GameSet], colours: &[&str], k: usize, bag: &mut Vec<u32>, res: &mut Vec<Vec<u32>>)
+{
+    if games.len() < k
+    {
+        return;
+    }
+
+    let (colour, rest) = match colours.split_first() {
+        Some(v) => v,
+        None => return res.push(bag.clone()),
+    };
+
+    let mut counts = games.iter().map(|g| g.get(colour)).collect::<Vec<u32>>();
+    counts.push(0);
+    counts.sort();
+    counts.dedup();
+
+    for count in counts
+    {
+        let left = games
+            .iter()
+            .filter(|g| g.get(colour) <= count)
+            .cloned()
+            .collect::<Vec<GameSet>>();
+        if left.len() < k
+        {
+            continue;
+        }
+
+        bag.push(count);
+        candidates(&left, rest, k, bag, res);
+        bag.pop();
+
+        // Any larger last count only adds cubes.
+        if rest.is_empty()
+        {
+            break;
+        }
+    }
+}
+
+/// The bags making at least `k` games possible from which no cube can be
+/// removed without losing that.
+pub fn frontier(games: &[Game], k: usize) -> Vec<GameSet>
+{
+    let colours = colours(games).into_iter().collect::<Vec<&str>>();
+    let sets = games
+        .iter()
+        .map(|g| g.get_max_set())
+        .collect::<Vec<GameSet>>();
+
+    let mut found = Vec::<Vec<u32>>::new();
+    candidates(&sets, &colours, k, &mut Vec::new(), &mut found);
+
+    let bags = found
+        .iter()
+        .map(|counts| GameSet {
+            counts: colours.iter().map(|c| c.to_string()).zip(counts.iter().copied()).collect(),
+        })
+        .collect::<Vec<GameSet>>();
+
+    let mut res = Vec::<GameSet>::new();
+    for (i, bag) in bags.iter().enumerate()
+    {
+        let dominated = bags
+            .iter()
+            .enumerate()
+            .any(|(j, other)| i != j && covers(bag, other) && (bag != other || j < i));
+        if !dominated
+        {
+            res.push(bag.clone());
+        }
+    }
+
+    res
+}
+
+/// Games by comma-separated ids, or every game for `all`.
+fn choose<'a>(games: &'a [Game], ids: Option<&String>) -> Result<Vec<&'a Game>, Error>
+{
+    let ids = match ids.map(|s| s.as_str()) {
+        None | Some("all") => return Ok(games.iter().collect()),
+        Some(v) => v,
+    };
+
+    ids.split(',')
+        .map(|id| {
+            let game = id.trim().parse::<u32>().ok()
+                .and_then(|id| games.iter().find(|g| g.id == id));
+            game.ok_or_else(|| Error::Usage(format!("no game `{id}`")))
+        })
+        .collect()
+}
+
+/// `bags count <budget> [ids|all]`, `bags list <budget> [ids|all] [limit]`
+/// and `bags frontier <k>`.
+pub fn run(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let games = Day2::parse(&source.load()?)?;
+    let mode = arg::<String>(args, 0, "mode")?;
+
+    match mode.as_str() {
+        "count" => {
+            let budget = arg::<u32>(args, 1, "budget")?;
+            let chosen = choose(&games, args.get(2))?;
+            Ok(count(&games, &chosen, budget)?.to_string())
+        },
+        "list" => {
+            let budget = arg::<u32>(args, 1, "budget")?;
+            let chosen = choose(&games, args.get(2))?;
+            let limit = match args.get(3) {
+                Some(_) => arg::<usize>(args, 3, "limit")?,
+                None => 100,
+            };
+
+            let mut res = Vec::<String>::new();
+            enumerate(&games, &chosen, budget, |bag| {
+                res.push(bag.to_string());
+                res.len() < limit
+            });
+
+            let total = count(&games, &chosen, budget)?;
+            if total > res.len() as u128
+            {
+                res.push(format!("... and {} more", total - res.len() as u128));
+            }
+            Ok(res.join("\n"))
+        },
+        "frontier" => {
+            let k = arg::<usize>(args, 1, "game count")?;
+            let res = frontier(&games, k)
+                .iter()
+                .map(|bag| {
+                    let possible = games.iter().filter(|g| bag.matches_requirements(&g.get_max_set())).count();
+                    format!("{bag} ({} cubes, {possible} games)", total(bag))
+                })
+                .collect::<Vec<String>>();
+            Ok(res.join("\n"))
+        },
+        _ => Err(Error::Usage(format!("unknown mode `{mode}`, expected `count`, `list` or `frontier`"))),
+    }
+}
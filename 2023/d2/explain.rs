@@ -0,0 +1,86 @@
+use common::command::arg;
+use common::input::Source;
+use common::{Error, Part, Solution};
+
+use super::{colours, read_bag, Day2, Game, GameSet, DEFAULT_BAG};
+
+/// The first draw, counted from 1, asking for more of a colour than `bag`
+/// holds, with that colour and count.
+pub(super) fn first_violation<'a>(game: &'a Game, bag: &GameSet) -> Option<(usize, &'a str, u32)>
+{
+    game.games
+        .iter()
+        .enumerate()
+        .find_map(|(i, set)| {
+            set.counts
+                .iter()
+                .find(|(colour, &count)| count > bag.get(colour))
+                .map(|(colour, &count)| (i + 1, colour.as_str(), count))
+        })
+}
+
+/// Each game with its max set and whether `bag` can produce it.
+pub fn explain_possible(games: &[Game], bag: &GameSet) -> Result<String, Error>
+{
+    let mut res = vec![format!("bag {bag}")];
+    let mut sum = 0u64;
+
+    for game in games
+    {
+        let verdict = match first_violation(game, bag) {
+            None => {
+                sum = sum
+                    .checked_add(u64::from(game.id))
+                    .ok_or_else(|| Error::Solve(format!("sum of possible ids overflows at game {}", game.id)))?;
+                "possible".to_string()
+            },
+            Some((draw, colour, count)) => {
+                format!("impossible, draw {draw} has {count} {colour} but the bag holds {}", bag.get(colour))
+            },
+        };
+        res.push(format!("Game {}: max {} -> {verdict}", game.id, game.get_max_set()));
+    }
+
+    res.push(format!("sum of possible ids {sum}"));
+    Ok(res.join("\n"))
+}
+
+/// Each game's power as the product of its smallest bag.
+pub fn explain_power(games: &[Game]) -> Result<String, Error>
+{
+    let colours = colours(games);
+    let mut res = Vec::<String>::new();
+    let mut sum = 0u64;
+
+    for game in games
+    {
+        let max_set = game.get_max_set();
+        let factors = colours
+            .iter()
+            .map(|c| format!("{} {c}", max_set.get(c)))
+            .collect::<Vec<String>>();
+        let power = max_set.power(colours.iter().copied())?;
+        sum = sum
+            .checked_add(power)
+            .ok_or_else(|| Error::Solve(format!("total power overflows at game {}", game.id)))?;
+        res.push(format!("Game {}: {} = {power}", game.id, factors.join(" * ")));
+    }
+
+    res.push(format!("total power {sum}"));
+    Ok(res.join("\n"))
+}
+
+/// `explain <part> [bag|@file]`
+pub fn explain(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let games = Day2::parse(&source.load()?)?;
+    let part = arg::<Part>(args, 0, "part")?;
+
+    match part {
+        Part::One => {
+            let bag = read_bag(args.get(1).map_or(DEFAULT_BAG, |s| s.as_str()))?;
+            explain_possible(&games, &bag)
+        },
+        Part::Two => explain_power(&games),
+    }
+}
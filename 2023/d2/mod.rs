@@ -0,0 +1,269 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use common::input::Source;
+use common::{input, Command, Error, ParseError, Solution};
+
+pub mod bags;
+pub mod explain;
+pub mod part1;
+pub mod part2;
+pub mod query;
+
+#[cfg(test)]
+mod tests;
+
+/// Cube counts by colour; colours that do not appear count as zero.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct GameSet
+{
+    counts: BTreeMap<String, u32>,
+}
+
+pub struct Game
+{
+    id: u32,
+    games: Vec<GameSet>,
+}
+
+impl GameSet
+{
+    pub fn get(&self, colour: &str) -> u32
+    {
+        self.counts.get(colour).copied().unwrap_or(0)
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &str>
+    {
+        self.counts.keys().map(|c| c.as_str())
+    }
+
+    /// Whether a bag of `self` can produce `other`.
+    fn matches_requirements(&self, other: &GameSet) -> bool
+    {
+        other.counts
+            .iter()
+            .all(|(colour, &count)| count <= self.get(colour))
+    }
+
+    /// The product of the counts of `colours`.
+    fn power<'a>(&self, colours: impl IntoIterator<Item = &'a str>) -> Result<u64, Error>
+    {
+        colours
+            .into_iter()
+            .try_fold(1u64, |a, c| a.checked_mul(u64::from(self.get(c))))
+            .ok_or_else(|| Error::Solve(format!("the power of {self} overflows")))
+    }
+
+    /// `set` is a slice of `line`, kept around for error spans.
+    fn parse(line: &str, set: &str) -> Result<GameSet, ParseError>
+    {
+        let mut res = GameSet::default();
+        res.parse_into(line, set)?;
+
+        Ok(res)
+    }
+
+    /// Adds the counts of `set` to `self`, rejecting colours it already has.
+    fn parse_into(&mut self, line: &str, set: &str) -> Result<(), ParseError>
+    {
+        let entries = set.split(",").map(|val| val.trim());
+
+        for entry in entries {
+            let (count, colour) = match entry.split_once(" ") {
+                Some(v) => v,
+                None => return Err(ParseError::new(line, entry, "`<count> <colour>`")),
+            };
+            let count: u32 = match count.parse() {
+                Ok(val) => val,
+                Err(_) => return Err(ParseError::new(line, count, "a cube count")),
+            };
+            if colour.is_empty() || !colour.chars().all(|c| c.is_alphabetic())
+            {
+                return Err(ParseError::new(line, colour, "a colour"));
+            }
+
+            if self.counts.insert(colour.to_string(), count).is_some()
+            {
+                return Err(ParseError::new(line, colour, "a colour not already listed"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads bag limits like `12 red, 13 green, 14 blue`, over one or more
+    /// lines.
+    pub fn parse_bag(text: &str) -> Result<GameSet, ParseError>
+    {
+        let mut res = GameSet::default();
+
+        for (i, line) in text.lines().enumerate().filter(|(_, l)| !l.trim().is_empty())
+        {
+            res.parse_into(line, line.trim()).map_err(|e| e.with_line(i + 1))?;
+        }
+
+        Ok(res)
+    }
+}
+
+impl Game
+{
+    fn new(id: u32, games: Vec<GameSet>) -> Game
+    {
+        Game
+        {
+            id,
+            games,
+        }
+    }
+
+    fn get_max_set(&self) -> GameSet
+    {
+        let mut res = GameSet::default();
+        for game in &self.games
+        {
+            for (colour, &count) in &game.counts
+            {
+                let max = res.counts.entry(colour.clone()).or_insert(0);
+                *max = (*max).max(count);
+            }
+        }
+
+        res
+    }
+
+    fn parse(line: &str) -> Result<Game, ParseError>
+    {
+        let rest = match line.strip_prefix("Game ") {
+            Some(v) => v,
+            None => {
+                let word = line.split(' ').next().unwrap_or(line);
+                return Err(ParseError::new(line, word, "`Game`"));
+            },
+        };
+
+        let (id, sets) = match rest.split_once(':') {
+            Some(v) => v,
+            None => return Err(ParseError::new(line, &rest[rest.len()..], "`:`")),
+        };
+        let id: u32 = match id.parse() {
+            Ok(val) => val,
+            Err(_) => return Err(ParseError::new(line, id, "a game id")),
+        };
+
+        let game_sets = sets.split(";")
+            .map(|entry| GameSet::parse(line, entry.trim()))
+            .collect::<Result<Vec<GameSet>, ParseError>>()?;
+
+        Ok(Self::new(id, game_sets))
+    }
+}
+
+impl fmt::Display for GameSet
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let counts = self.counts
+            .iter()
+            .map(|(colour, count)| format!("{count} {colour}"))
+            .collect::<Vec<String>>();
+        write!(f, "{}", counts.join(", "))
+    }
+}
+
+impl fmt::Debug for GameSet
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "({self})")
+    }
+}
+
+impl fmt::Display for Game
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "({}, {:?})", self.id, self.games)
+    }
+}
+
+/// Every colour any game draws.
+fn colours(games: &[Game]) -> BTreeSet<&str>
+{
+    games
+        .iter()
+        .flat_map(|game| game.games.iter())
+        .flat_map(|set| set.colours())
+        .collect()
+}
+
+/// The bag of part 1.
+pub const DEFAULT_BAG: &str = "12 red, 13 green, 14 blue";
+
+/// `@path` reads the bag from a file, anything else is the bag itself.
+fn read_bag(arg: &str) -> Result<GameSet, Error>
+{
+    match arg.strip_prefix('@') {
+        Some(path) => {
+            let source = Source::Path(path.into());
+            GameSet::parse_bag(&source.load()?).map_err(|e| Error::from(e).with_file(path))
+        },
+        None => GameSet::parse_bag(arg).map_err(|e| Error::Usage(format!("invalid bag: {e}"))),
+    }
+}
+
+/// `possible [bag|@file]`: the part 1 answer for another bag.
+fn possible(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let games = Day2::parse(&source.load()?)?;
+    let bag = read_bag(args.first().map_or(DEFAULT_BAG, |s| s.as_str()))?;
+
+    Ok(part1::solve(&games, &bag)?.to_string())
+}
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "bags",
+        usage: "count <budget> [ids|all] | list <budget> [ids|all] [limit] | frontier <k>",
+        run: bags::run,
+    },
+    Command {
+        name: "explain",
+        usage: "<part> [bag|@file]",
+        run: explain::explain,
+    },
+    Command {
+        name: "possible",
+        usage: "[bag|@file]",
+        run: possible,
+    },
+    Command {
+        name: "query",
+        usage: "<aggregate> <expr> [where <predicate> [and <predicate>]...] [by <expr>] | part1 [bag|@file] | part2",
+        run: query::run,
+    },
+];
+
+pub struct Day2;
+
+impl Solution for Day2
+{
+    type Input = Vec<Game>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        Ok(input::parse_lines(input, Game::parse)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        part1::solve(input, &GameSet::parse_bag(DEFAULT_BAG)?)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        part2::solve(input)
+    }
+}
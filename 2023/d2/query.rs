@@ -0,0 +1,475 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use common::input::Source;
+use common::{Error, ParseError, Solution};
+
+use super::explain::first_violation;
+use super::{colours, read_bag, Day2, Game, GameSet, DEFAULT_BAG};
+
+/// A number computed for each game.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr
+{
+    Id,
+    /// The product of the maximum counts of every colour of the input.
+    Power,
+    Draws,
+    /// The maximum count of a colour, `*` standing for each colour in turn.
+    Max(String),
+    /// The first draw, counted from 1, the bag cannot produce, 0 if none.
+    FirstViolation(GameSet),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op
+{
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Predicate
+{
+    Compare(Expr, Op, u64),
+    /// Some draw has a count of the colour matching.
+    AnyDraw(String, Op, u64),
+    AllDraws(String, Op, u64),
+    /// The bag can produce every draw.
+    Fits(GameSet),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate
+{
+    Count,
+    Sum,
+    Product,
+    Min,
+    Max,
+    /// Every value, in game order.
+    List,
+}
+
+/// `<aggregate> <expr> [where <predicate> [and <predicate>]...] [by <expr>]`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Query
+{
+    pub aggregate: Aggregate,
+    pub value: Expr,
+    pub filters: Vec<Predicate>,
+    pub group: Option<Expr>,
+}
+
+/// One result of a query: the colour `*` stood for, the group key and the
+/// aggregate, empty when no game was left to aggregate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Row
+{
+    pub colour: Option<String>,
+    pub group: Option<u64>,
+    /// A single value, except for `list`.
+    pub values: Vec<u64>,
+}
+
+impl Op
+{
+    fn apply(self, a: u64, b: u64) -> bool
+    {
+        match self {
+            Op::Lt => a < b,
+            Op::Le => a <= b,
+            Op::Gt => a > b,
+            Op::Ge => a >= b,
+            Op::Eq => a == b,
+            Op::Ne => a != b,
+        }
+    }
+}
+
+impl Expr
+{
+    fn eval(&self, game: &Game, colours: &BTreeSet<&str>) -> Result<u64, Error>
+    {
+        let res = match self {
+            Expr::Id => u64::from(game.id),
+            Expr::Power => game.get_max_set().power(colours.iter().copied())?,
+            Expr::Draws => game.games.len() as u64,
+            Expr::Max(colour) => u64::from(game.get_max_set().get(colour)),
+            Expr::FirstViolation(bag) => first_violation(game, bag).map_or(0, |(draw, _, _)| draw as u64),
+        };
+
+        Ok(res)
+    }
+
+    fn with_colour(&self, colour: &str) -> Expr
+    {
+        match self {
+            Expr::Max(c) if c == "*" => Expr::Max(colour.to_string()),
+            e => e.clone(),
+        }
+    }
+
+    fn is_per_colour(&self) -> bool
+    {
+        *self == Expr::Max("*".to_string())
+    }
+}
+
+impl Predicate
+{
+    fn matches(&self, game: &Game, colours: &BTreeSet<&str>) -> Result<bool, Error>
+    {
+        let res = match self {
+            Predicate::Compare(expr, op, n) => op.apply(expr.eval(game, colours)?, *n),
+            Predicate::AnyDraw(colour, op, n) => game.games.iter().any(|set| op.apply(u64::from(set.get(colour)), *n)),
+            Predicate::AllDraws(colour, op, n) => game.games.iter().all(|set| op.apply(u64::from(set.get(colour)), *n)),
+            Predicate::Fits(bag) => bag.matches_requirements(&game.get_max_set()),
+        };
+
+        Ok(res)
+    }
+}
+
+impl Aggregate
+{
+    fn apply(self, values: Vec<u64>) -> Result<Vec<u64>, Error>
+    {
+        let overflow = || Error::Solve(format!("{self:?} of {} values overflows", values.len()));
+
+        let res = match self {
+            Aggregate::Count => Some(values.len() as u64),
+            Aggregate::Sum => Some(values.iter().try_fold(0u64, |a, &b| a.checked_add(b)).ok_or_else(overflow)?),
+            Aggregate::Product => Some(values.iter().try_fold(1u64, |a, &b| a.checked_mul(b)).ok_or_else(overflow)?),
+            Aggregate::Min => values.iter().min().copied(),
+            Aggregate::Max => values.iter().max().copied(),
+            Aggregate::List => return Ok(values),
+        };
+
+        Ok(res.into_iter().collect())
+    }
+}
+
+/// Splits the query into words, keeping them as slices for error spans.
+struct Words<'a>
+{
+    text: &'a str,
+    words: std::iter::Peekable<std::str::SplitWhitespace<'a>>,
+}
+
+impl<'a> Words<'a>
+{
+    fn next(&mut self, expected: &str) -> Result<&'a str, ParseError>
+    {
+        match self.words.next() {
+            Some(v) => Ok(v),
+            None => Err(ParseError::new(self.text, &self.text[self.text.len()..], expected)),
+        }
+    }
+
+    fn error(&self, word: &str, expected: &str) -> ParseError
+    {
+        ParseError::new(self.text, word, expected)
+    }
+
+    fn number(&mut self) -> Result<u64, ParseError>
+    {
+        let word = self.next("a number")?;
+        word.parse().map_err(|_| self.error(word, "a number"))
+    }
+
+    fn op(&mut self) -> Result<Op, ParseError>
+    {
+        let expected = "`<`, `<=`, `>`, `>=`, `==` or `!=`";
+        let word = self.next(expected)?;
+        match word {
+            "<" => Ok(Op::Lt),
+            "<=" => Ok(Op::Le),
+            ">" => Ok(Op::Gt),
+            ">=" => Ok(Op::Ge),
+            "==" => Ok(Op::Eq),
+            "!=" => Ok(Op::Ne),
+            _ => Err(self.error(word, expected)),
+        }
+    }
+
+    /// The words of a bag up to the next keyword or operator, the default
+    /// bag when there are none.
+    fn bag(&mut self) -> Result<GameSet, ParseError>
+    {
+        let keywords = ["where", "and", "by", "<", "<=", ">", ">=", "==", "!="];
+
+        let mut bag = Vec::<&str>::new();
+        while let Some(&word) = self.words.peek().filter(|w| !keywords.contains(w))
+        {
+            bag.push(word);
+            self.words.next();
+        }
+        let bag = match bag.is_empty() {
+            true => DEFAULT_BAG.to_string(),
+            false => bag.join(" "),
+        };
+
+        GameSet::parse_bag(&bag)
+    }
+
+    fn expr(&mut self) -> Result<Expr, ParseError>
+    {
+        let expected = "`id`, `power`, `draws`, `max.<colour>` or `first_violation [bag]`";
+        let word = self.next(expected)?;
+        match word {
+            "id" => Ok(Expr::Id),
+            "power" => Ok(Expr::Power),
+            "draws" => Ok(Expr::Draws),
+            "first_violation" => Ok(Expr::FirstViolation(self.bag()?)),
+            _ => match word.strip_prefix("max.") {
+                Some(colour) if !colour.is_empty() => Ok(Expr::Max(colour.to_string())),
+                _ => Err(self.error(word, expected)),
+            },
+        }
+    }
+
+    fn draw_colour(&mut self) -> Result<String, ParseError>
+    {
+        let word = self.next("`draw.<colour>`")?;
+        match word.strip_prefix("draw.") {
+            Some(colour) if !colour.is_empty() => Ok(colour.to_string()),
+            _ => Err(self.error(word, "`draw.<colour>`")),
+        }
+    }
+
+    fn predicate(&mut self) -> Result<Predicate, ParseError>
+    {
+        match self.words.peek() {
+            Some(&"any") => {
+                self.words.next();
+                Ok(Predicate::AnyDraw(self.draw_colour()?, self.op()?, self.number()?))
+            },
+            Some(&"all") => {
+                self.words.next();
+                Ok(Predicate::AllDraws(self.draw_colour()?, self.op()?, self.number()?))
+            },
+            Some(&"fits") => {
+                self.words.next();
+                self.bag().map(Predicate::Fits)
+            },
+            _ => Ok(Predicate::Compare(self.expr()?, self.op()?, self.number()?)),
+        }
+    }
+}
+
+impl Query
+{
+    /// Reads `<aggregate> <expr> [where <predicate> [and <predicate>]...]
+    /// [by <expr>]`, where `count` takes no expression.
+    pub fn parse(text: &str) -> Result<Query, ParseError>
+    {
+        let mut words = Words { text, words: text.split_whitespace().peekable() };
+
+        let expected = "`count`, `sum`, `product`, `min`, `max` or `list`";
+        let word = words.next(expected)?;
+        let aggregate = match word {
+            "count" => Aggregate::Count,
+            "sum" => Aggregate::Sum,
+            "product" => Aggregate::Product,
+            "min" => Aggregate::Min,
+            "max" => Aggregate::Max,
+            "list" => Aggregate::List,
+            _ => return Err(words.error(word, expected)),
+        };
+        let value = match aggregate {
+            Aggregate::Count => Expr::Id,
+            _ => words.expr()?,
+        };
+
+        let mut res = Query { aggregate, value, filters: Vec::new(), group: None };
+        if words.words.peek() == Some(&"where")
+        {
+            words.words.next();
+            res.filters.push(words.predicate()?);
+            while words.words.peek() == Some(&"and")
+            {
+                words.words.next();
+                res.filters.push(words.predicate()?);
+            }
+        }
+        if words.words.peek() == Some(&"by")
+        {
+            words.words.next();
+            res.group = Some(words.expr()?);
+        }
+        if let Some(word) = words.words.next()
+        {
+            return Err(words.error(word, "`where`, `and` or `by`"));
+        }
+
+        Ok(res)
+    }
+
+    /// Part 1: the ids of the games `bag` can produce.
+    pub fn part1(bag: &GameSet) -> Query
+    {
+        Query {
+            aggregate: Aggregate::Sum,
+            value: Expr::Id,
+            filters: vec![Predicate::Fits(bag.clone())],
+            group: None,
+        }
+    }
+
+    /// Part 2: the powers of the smallest bags of each game.
+    pub fn part2() -> Query
+    {
+        Query {
+            aggregate: Aggregate::Sum,
+            value: Expr::Power,
+            filters: Vec::new(),
+            group: None,
+        }
+    }
+
+    fn with_colour(&self, colour: &str) -> Query
+    {
+        let filters = self.filters
+            .iter()
+            .map(|p| match p {
+                Predicate::Compare(expr, op, n) => Predicate::Compare(expr.with_colour(colour), *op, *n),
+                p => p.clone(),
+            })
+            .collect();
+
+        Query {
+            aggregate: self.aggregate,
+            value: self.value.with_colour(colour),
+            filters,
+            group: self.group.as_ref().map(|e| e.with_colour(colour)),
+        }
+    }
+
+    fn is_per_colour(&self) -> bool
+    {
+        self.value.is_per_colour()
+            || self.group.as_ref().is_some_and(|e| e.is_per_colour())
+            || self.filters.iter().any(|p| matches!(p, Predicate::Compare(e, _, _) if e.is_per_colour()))
+    }
+
+    /// One row per group, repeated for each colour when the query uses
+    /// `max.*`.
+    pub fn run(&self, games: &[Game]) -> Result<Vec<Row>, Error>
+    {
+        let colours = colours(games);
+
+        if self.is_per_colour()
+        {
+            let mut res = Vec::<Row>::new();
+            for colour in &colours
+            {
+                for mut row in self.with_colour(colour).run_colour(games, &colours)?
+                {
+                    row.colour = Some(colour.to_string());
+                    res.push(row);
+                }
+            }
+
+            return Ok(res);
+        }
+
+        self.run_colour(games, &colours)
+    }
+
+    /// The single value of an ungrouped query.
+    pub fn total(&self, games: &[Game]) -> Result<u64, Error>
+    {
+        let rows = self.run(games)?;
+        Ok(rows.first().and_then(|r| r.values.first()).copied().unwrap_or(0))
+    }
+
+    /// Whether `game` passes every filter.
+    fn keeps(&self, game: &Game, colours: &BTreeSet<&str>) -> Result<bool, Error>
+    {
+        for predicate in &self.filters
+        {
+            if !predicate.matches(game, colours)?
+            {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    fn run_colour(&self, games: &[Game], colours: &BTreeSet<&str>) -> Result<Vec<Row>, Error>
+    {
+        let mut groups = BTreeMap::<Option<u64>, Vec<u64>>::new();
+        if self.group.is_none()
+        {
+            groups.insert(None, Vec::new());
+        }
+
+        for game in games
+        {
+            if !self.keeps(game, colours)?
+            {
+                continue;
+            }
+
+            let key = self.group.as_ref().map(|e| e.eval(game, colours)).transpose()?;
+            groups.entry(key).or_default().push(self.value.eval(game, colours)?);
+        }
+
+        groups
+            .into_iter()
+            .map(|(group, values)| Ok(Row { colour: None, group, values: self.aggregate.apply(values)? }))
+            .collect()
+    }
+}
+
+impl fmt::Display for Row
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        if let Some(colour) = &self.colour
+        {
+            write!(f, "{colour} ")?;
+        }
+        if let Some(group) = self.group
+        {
+            write!(f, "{group}: ")?;
+        }
+
+        match self.values.is_empty() {
+            true => write!(f, "-"),
+            false => {
+                let values = self.values.iter().map(|v| v.to_string()).collect::<Vec<String>>();
+                write!(f, "{}", values.join(", "))
+            },
+        }
+    }
+}
+
+/// `query <query>|part1 [bag|@file]|part2`: runs a query over the games.
+pub fn run(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let games = Day2::parse(&source.load()?)?;
+
+    let query = match args.first().map(|s| s.as_str()) {
+        Some("part1") => Query::part1(&read_bag(args.get(1).map_or(DEFAULT_BAG, |s| s.as_str()))?),
+        Some("part2") => Query::part2(),
+        _ => {
+            let text = args.join(" ");
+            Query::parse(&text).map_err(|e| Error::Usage(format!("invalid query: {}", e.with_line(1))))?
+        },
+    };
+
+    let rows = query
+        .run(&games)?
+        .iter()
+        .map(|row| row.to_string())
+        .collect::<Vec<String>>();
+
+    Ok(rows.join("\n"))
+}
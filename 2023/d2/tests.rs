@@ -0,0 +1,161 @@
+use super::*;
+use crate::testing::Lcg;
+
+const EXAMPLE: &str = include_str!("../tests/examples/2.txt");
+
+#[test]
+fn games_take_any_colour()
+{
+    let games = Day2::parse("Game 1: 3 pink, 2 red; 1 pink, 4 teal\nGame 2: 5 red\n").unwrap();
+
+    assert_eq!(games[0].get_max_set().to_string(), "3 pink, 2 red, 4 teal");
+    assert_eq!(part2::solve(&games).unwrap(), 24);
+
+    let bag = GameSet::parse_bag("3 pink\n4 teal, 2 red").unwrap();
+    assert_eq!(part1::solve(&games, &bag).unwrap(), 1);
+}
+
+#[test]
+fn bag_limits_are_configurable()
+{
+    let games = Day2::parse(EXAMPLE).unwrap();
+
+    assert_eq!(part1::solve(&games, &GameSet::parse_bag(DEFAULT_BAG).unwrap()).unwrap(), 8);
+    assert_eq!(part1::solve(&games, &GameSet::parse_bag("20 red, 20 green, 20 blue").unwrap()).unwrap(), 15);
+
+    let error = GameSet::parse_bag("1 red\n2 bl-ue").unwrap_err();
+    assert_eq!(error.line, 2);
+}
+
+#[test]
+fn repeated_colours_are_rejected()
+{
+    let error = Day2::parse("Game 1: 3 red; 1 blue, 3 red, 4 red\n").map(|_| ()).unwrap_err();
+    assert!(error.to_string().starts_with("expected a colour not already listed, found `red`"), "{error}");
+
+    let error = GameSet::parse_bag("4 red, 2 blue\n1 red").unwrap_err();
+    assert_eq!((error.line, error.columns.clone()), (2, 2..5));
+
+    // The same colour in different draws is fine.
+    assert!(Day2::parse("Game 1: 3 red; 4 red\n").is_ok());
+}
+
+#[test]
+fn queries_filter_aggregate_and_group()
+{
+    let games = Day2::parse(EXAMPLE).unwrap();
+    let run = |text: &str| {
+        query::Query::parse(text).unwrap()
+            .run(&games).unwrap()
+            .iter()
+            .map(|row| row.to_string())
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(run("sum id where fits"), vec!["8"]);
+    assert_eq!(run("sum id where fits 20 red, 20 green, 20 blue"), vec!["15"]);
+    assert_eq!(run("sum power"), vec!["2286"]);
+    assert_eq!(run("count where any draw.red > 12"), vec!["2"]);
+    assert_eq!(run("max max.*"), vec!["blue 15", "green 13", "red 20"]);
+    assert_eq!(run("sum power by draws"), vec!["2: 36", "3: 2250"]);
+    assert_eq!(run("min id where id > 5"), vec!["-"]);
+    assert_eq!(run("list id where fits"), vec!["1, 2, 5"]);
+    assert_eq!(run("list id where first_violation > 0 by first_violation"), vec!["1: 3", "3: 4"]);
+    assert_eq!(run("list first_violation 5 red, 5 green, 5 blue"), vec!["2, 0, 1, 1, 1"]);
+
+    let error = query::Query::parse("sum id where max.red ~ 3").unwrap_err();
+    assert_eq!(error.found, "`~`");
+}
+
+#[test]
+fn bags_are_counted_and_listed()
+{
+    let games = Day2::parse(EXAMPLE).unwrap();
+    let all = games.iter().collect::<Vec<&Game>>();
+
+    assert_eq!(bags::minimum(all.iter().copied()).to_string(), "15 blue, 13 green, 20 red");
+    assert_eq!(bags::count(&games, &all, 47).unwrap(), 0);
+    assert_eq!(bags::count(&games, &all, 50).unwrap(), 6);
+
+    let mut listed = Vec::<String>::new();
+    bags::enumerate(&games, &all, 50, |bag| {
+        listed.push(bag.to_string());
+        true
+    });
+    assert_eq!(listed.len(), 6);
+    assert_eq!(listed[0], "15 blue, 13 green, 22 red");
+}
+
+#[test]
+fn frontier_matches_brute_force()
+{
+    let mut rng = Lcg::new(7);
+    let mut next = |max: u64| rng.next(max);
+
+    let lines = (1..=8)
+        .map(|id| format!("Game {id}: {} red, {} green; {} blue", next(6), next(6), next(6)))
+        .collect::<Vec<String>>();
+    let games = Day2::parse(&lines.join("\n")).unwrap();
+    let possible = |bag: &GameSet| games.iter().filter(|g| bag.matches_requirements(&g.get_max_set())).count();
+
+    for k in 0..=games.len()
+    {
+        let bag = |r: u32, g: u32, b: u32| GameSet::parse_bag(&format!("{b} blue, {g} green, {r} red")).unwrap();
+        let fits = |r: u32, g: u32, b: u32| possible(&bag(r, g, b)) >= k;
+
+        let mut expected = Vec::<String>::new();
+        for r in 0..6u32
+        {
+            for g in 0..6u32
+            {
+                for b in 0..6u32
+                {
+                    let minimal = (r == 0 || !fits(r - 1, g, b))
+                        && (g == 0 || !fits(r, g - 1, b))
+                        && (b == 0 || !fits(r, g, b - 1));
+                    if fits(r, g, b) && minimal
+                    {
+                        expected.push(bag(r, g, b).to_string());
+                    }
+                }
+            }
+        }
+        expected.sort();
+
+        let mut res = bags::frontier(&games, k).iter().map(|b| b.to_string()).collect::<Vec<String>>();
+        res.sort();
+        assert_eq!(res, expected, "k = {k}\n{}", lines.join("\n"));
+    }
+}
+
+#[test]
+fn explain_names_the_draw_that_breaks_the_bag()
+{
+    let games = Day2::parse(EXAMPLE).unwrap();
+
+    let res = explain::explain_possible(&games, &GameSet::parse_bag(DEFAULT_BAG).unwrap()).unwrap();
+    assert!(res.contains("Game 3: max 6 blue, 13 green, 20 red -> impossible, draw 1 has 20 red but the bag holds 12"), "{res}");
+    assert!(res.contains("Game 5: max 2 blue, 3 green, 6 red -> possible"), "{res}");
+    assert!(res.ends_with("sum of possible ids 8"), "{res}");
+
+    let res = explain::explain_power(&games).unwrap();
+    assert!(res.starts_with("Game 1: 6 blue * 2 green * 4 red = 48"), "{res}");
+    assert!(res.ends_with("total power 2286"), "{res}");
+
+    // Each power fits but their sum does not, then a power itself overflows.
+    let games = Day2::parse("Game 1: 4000000000 red, 4000000000 blue\nGame 2: 4000000000 red, 4000000000 blue\n").unwrap();
+    assert!(matches!(explain::explain_power(&games), Err(Error::Solve(e)) if e.contains("total power")));
+
+    let games = Day2::parse("Game 1: 4000000000 red, 4000000000 blue, 2 green\n").unwrap();
+    assert!(matches!(explain::explain_power(&games), Err(Error::Solve(e)) if e.contains("the power of")));
+}
+
+#[test]
+fn power_overflow_is_reported()
+{
+    let games = Day2::parse("Game 1: 100000 red, 100000 green, 100000 blue, 100000 purple\n").unwrap();
+
+    assert!(matches!(Day2::part2(&games), Err(Error::Solve(_))));
+    assert!(matches!(query::Query::parse("max power").unwrap().run(&games), Err(Error::Solve(_))));
+    assert!(matches!(query::Query::parse("count where power > 5").unwrap().run(&games), Err(Error::Solve(_))));
+}
@@ -0,0 +1,258 @@
+use std::fmt;
+
+use common::{Command, Error, Grid, ParseError, Point, Solution};
+
+pub mod part1;
+pub mod part2;
+pub mod query;
+pub mod render;
+pub mod stream;
+
+#[cfg(test)]
+mod tests;
+
+/// A number of the schematic and the cells it spans.
+#[derive(Clone)]
+pub struct Value
+{
+    pub value: u32,
+    pub positions: Vec<Point>,
+}
+
+/// A cell that is neither a digit nor a `.`, with the numbers around it.
+#[derive(Clone, Debug)]
+pub struct Symbol
+{
+    pub kind: char,
+    pub position: Point,
+    /// Indices in `Schematic::values`, each once and in reading order.
+    pub parts: Vec<usize>,
+}
+
+impl fmt::Debug for Value
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "({}, {{{}, {}}})", self.value, self.positions.first().unwrap(), self.positions.last().unwrap())
+    }
+}
+
+/// What a blank line within a schematic is reported as, read whole or
+/// streamed.
+const BLANK_ROW: &str = "a schematic row, not a blank line";
+
+pub struct Schematic
+{
+    grid: Grid<char>,
+    values: Vec<Value>,
+    symbols: Vec<Symbol>,
+}
+
+impl Schematic
+{
+    /// Reads the runs of digits of row `y` as numbers.
+    fn parse_values(grid: &Grid<char>, y: usize, line: &str) -> Result<Vec<Value>, ParseError>
+    {
+        let mut res = Vec::<Value>::new();
+        let row = grid.row(y);
+
+        let mut x = 0;
+        while x < row.len()
+        {
+            if !row[x].is_ascii_digit()
+            {
+                x += 1;
+                continue;
+            }
+
+            let start = x;
+            while x < row.len() && row[x].is_ascii_digit()
+            {
+                x += 1;
+            }
+
+            // Digits are ASCII, so the run is `x - start` bytes long.
+            let offset = line.char_indices().nth(start).unwrap().0;
+            let digits = &line[offset..offset + x - start];
+            let value = digits
+                .parse()
+                .map_err(|_| ParseError::new(line, digits, "a number fitting in u32").with_line(y + 1))?;
+            res.push(Value {
+                value,
+                positions: (start..x).map(|x| Point::new(x, y)).collect(),
+            });
+        }
+
+        Ok(res)
+    }
+
+    fn parse(input: &str) -> Result<Schematic, ParseError>
+    {
+        if let Some((y, line)) = input.lines().enumerate().find(|(_, line)| line.trim().is_empty())
+        {
+            return Err(ParseError::new(line, line, BLANK_ROW).with_line(y + 1));
+        }
+        let grid = Grid::parse(input, |c| c)?;
+
+        let mut values = Vec::<Value>::new();
+        for (y, line) in input.lines().enumerate()
+        {
+            values.extend(Schematic::parse_values(&grid, y, line)?);
+        }
+
+        // The index in `values` of the number covering each cell.
+        let mut cells = grid.map(|_| None);
+        for (i, value) in values.iter().enumerate()
+        {
+            for &pos in &value.positions
+            {
+                cells[pos] = Some(i);
+            }
+        }
+
+        let symbols = grid
+            .points()
+            .filter(|&p| grid[p] != '.' && !grid[p].is_ascii_digit())
+            .map(|p| {
+                let mut parts = grid
+                    .neighbours8(p)
+                    .filter_map(|n| cells[n])
+                    .collect::<Vec<usize>>();
+                // A number lies on one row, so its cells come out together.
+                parts.dedup();
+
+                Symbol { kind: grid[p], position: p, parts }
+            })
+            .collect();
+
+        Ok(Schematic {
+            grid,
+            values,
+            symbols,
+        })
+    }
+
+    pub fn grid(&self) -> &Grid<char>
+    {
+        &self.grid
+    }
+
+    pub fn values(&self) -> &[Value]
+    {
+        &self.values
+    }
+
+    pub fn symbols(&self) -> &[Symbol]
+    {
+        &self.symbols
+    }
+
+    /// Whether each number touches a symbol `touches` accepts.
+    fn touched<F>(&self, touches: F) -> Vec<bool>
+    where
+        F: Fn(&Symbol) -> bool,
+    {
+        let mut res = vec![false; self.values.len()];
+        for symbol in self.symbols.iter().filter(|s| touches(s))
+        {
+            for &i in &symbol.parts
+            {
+                res[i] = true;
+            }
+        }
+
+        res
+    }
+
+    /// Numbers touching at least one symbol `touches` accepts, each once and
+    /// in reading order.
+    pub fn parts<F>(&self, touches: F) -> Vec<&Value>
+    where
+        F: Fn(&Symbol) -> bool,
+    {
+        self.values
+            .iter()
+            .zip(self.touched(touches))
+            .filter(|(_, touched)| *touched)
+            .map(|(value, _)| value)
+            .collect()
+    }
+
+    /// Numbers touching no symbol at all.
+    pub fn loose(&self) -> Vec<&Value>
+    {
+        self.values
+            .iter()
+            .zip(self.touched(|_| true))
+            .filter(|(_, touched)| !*touched)
+            .map(|(value, _)| value)
+            .collect()
+    }
+
+    /// Symbols of `kind` touching exactly `count` numbers.
+    pub fn gears(&self, kind: char, count: usize) -> impl Iterator<Item = &Symbol>
+    {
+        self.symbols
+            .iter()
+            .filter(move |s| s.kind == kind && s.parts.len() == count)
+    }
+
+    /// The product of the numbers around `symbol`.
+    pub fn ratio(&self, symbol: &Symbol) -> Result<u64, Error>
+    {
+        symbol.parts
+            .iter()
+            .try_fold(1u64, |a, &i| a.checked_mul(u64::from(self.values[i].value)))
+            .ok_or_else(|| Error::Solve(format!("the ratio of the `{}` at {} overflows", symbol.kind, symbol.position)))
+    }
+
+    /// The sum of the ratios of the `gears(kind, count)`.
+    pub fn ratios(&self, kind: char, count: usize) -> Result<u64, Error>
+    {
+        self.gears(kind, count).try_fold(0u64, |sum, gear| {
+            sum.checked_add(self.ratio(gear)?)
+                .ok_or_else(|| Error::Solve(format!("the sum of the ratios overflows at {}", gear.position)))
+        })
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3
+{
+    type Input = Schematic;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        Ok(Schematic::parse(input)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        Ok(part1::solve(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        part2::solve(input)
+    }
+}
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "query",
+        usage: "sum [kinds] | gears <kind> <count> | loose",
+        run: query::run,
+    },
+    Command {
+        name: "render",
+        usage: "<ansi|html>",
+        run: render::run,
+    },
+    Command {
+        name: "stream",
+        usage: "<part>",
+        run: stream::run,
+    },
+];
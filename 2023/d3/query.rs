@@ -0,0 +1,36 @@
+use common::command::arg;
+use common::input::Source;
+use common::{Error, Solution};
+
+use super::Day3;
+
+/// `query sum [kinds]`: the numbers touching a symbol of `kinds`, or any
+/// symbol. `query gears <kind> <count>`: the ratios of the `kind` symbols
+/// touching exactly `count` numbers. `query loose`: numbers touching none.
+pub fn run(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let schematic = Day3::parse(&source.load()?)?;
+    let query = arg::<String>(args, 0, "query")?;
+
+    match query.as_str() {
+        "sum" => {
+            let kinds = args.get(1);
+            let parts = schematic.parts(|s| kinds.is_none_or(|k| k.contains(s.kind)));
+            Ok(parts.iter().map(|v| u64::from(v.value)).sum::<u64>().to_string())
+        },
+        "gears" => {
+            let kind = arg::<char>(args, 1, "symbol kind")?;
+            let count = arg::<usize>(args, 2, "part count")?;
+            Ok(schematic.ratios(kind, count)?.to_string())
+        },
+        "loose" => {
+            let res = schematic
+                .loose()
+                .iter()
+                .map(|v| format!("{} at {}", v.value, v.positions[0]))
+                .collect::<Vec<String>>();
+            Ok(res.join("\n"))
+        },
+        _ => Err(Error::Usage(format!("unknown query `{query}`, expected `sum`, `gears` or `loose`"))),
+    }
+}
@@ -0,0 +1,194 @@
+use common::command::arg;
+use common::input::Source;
+use common::{Error, Grid, Solution};
+
+use super::{Day3, Schematic};
+
+/// How a cell is drawn.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style
+{
+    Blank,
+    /// A number touching no symbol.
+    Loose,
+    /// A number counted by part 1.
+    Part,
+    /// A number of a part 2 gear.
+    Gear,
+    Symbol,
+    GearSymbol,
+}
+
+impl Style
+{
+    fn ansi(self) -> &'static str
+    {
+        match self {
+            Style::Blank => "\x1b[2m",
+            Style::Loose => "\x1b[31m",
+            Style::Part => "\x1b[32m",
+            Style::Gear => "\x1b[33m",
+            Style::Symbol => "\x1b[1;35m",
+            Style::GearSymbol => "\x1b[1;33m",
+        }
+    }
+
+    fn class(self) -> &'static str
+    {
+        match self {
+            Style::Blank => "blank",
+            Style::Loose => "loose",
+            Style::Part => "part",
+            Style::Gear => "gear",
+            Style::Symbol => "symbol",
+            Style::GearSymbol => "gear-symbol",
+        }
+    }
+}
+
+fn styles(schematic: &Schematic) -> Grid<Style>
+{
+    let mut res = schematic.grid().map(|_| Style::Blank);
+
+    for value in schematic.parts(|_| true)
+    {
+        for &p in &value.positions
+        {
+            res[p] = Style::Part;
+        }
+    }
+    for value in schematic.loose()
+    {
+        for &p in &value.positions
+        {
+            res[p] = Style::Loose;
+        }
+    }
+    for symbol in schematic.symbols()
+    {
+        res[symbol.position] = Style::Symbol;
+    }
+    for gear in schematic.gears('*', 2)
+    {
+        res[gear.position] = Style::GearSymbol;
+        for &i in &gear.parts
+        {
+            for &p in &schematic.values()[i].positions
+            {
+                res[p] = Style::Gear;
+            }
+        }
+    }
+
+    res
+}
+
+/// Each row as runs of cells sharing a style.
+fn runs(schematic: &Schematic) -> Vec<Vec<(Style, String)>>
+{
+    let styles = styles(schematic);
+
+    schematic.grid()
+        .rows()
+        .zip(styles.rows())
+        .map(|(cells, styles)| {
+            let mut res = Vec::<(Style, String)>::new();
+            for (&c, &style) in cells.iter().zip(styles)
+            {
+                match res.last_mut() {
+                    Some((last, text)) if *last == style => text.push(c),
+                    _ => res.push((style, c.to_string())),
+                }
+            }
+
+            res
+        })
+        .collect()
+}
+
+pub fn ansi(schematic: &Schematic) -> String
+{
+    runs(schematic)
+        .iter()
+        .map(|row| {
+            let line = row
+                .iter()
+                .map(|(style, text)| format!("{}{text}", style.ansi()))
+                .collect::<String>();
+            format!("{line}\x1b[0m")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn escape(text: &str) -> String
+{
+    text
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// A standalone page with the schematic and a legend.
+pub fn html(schematic: &Schematic) -> String
+{
+    let rows = runs(schematic)
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|(style, text)| format!("<span class=\"{}\">{}</span>", style.class(), escape(text)))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let legend = [
+        (Style::Part, "part number"),
+        (Style::Gear, "gear number"),
+        (Style::Loose, "touches no symbol"),
+        (Style::Symbol, "symbol"),
+        (Style::GearSymbol, "gear"),
+    ]
+        .iter()
+        .map(|(style, name)| format!("<span class=\"{}\">{name}</span>", style.class()))
+        .collect::<Vec<String>>()
+        .join(" ");
+
+    format!(r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Schematic</title>
+<style>
+body {{ background: #1e1e1e; color: #d4d4d4; font-family: monospace; }}
+.blank {{ color: #555555; }}
+.loose {{ color: #f44747; }}
+.part {{ color: #6a9955; }}
+.gear {{ color: #dcdcaa; }}
+.symbol {{ color: #c586c0; font-weight: bold; }}
+.gear-symbol {{ color: #dcdcaa; font-weight: bold; background: #3a3a1e; }}
+</style>
+</head>
+<body>
+<p>{legend}</p>
+<pre>
+{rows}
+</pre>
+</body>
+</html>
+"#)
+}
+
+/// `render <ansi|html>`: the schematic coloured by what each number counts
+/// for.
+pub fn run(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let schematic = Day3::parse(&source.load()?)?;
+    let format = arg::<String>(args, 0, "format")?;
+
+    match format.as_str() {
+        "ansi" => Ok(ansi(&schematic)),
+        "html" => Ok(html(&schematic)),
+        _ => Err(Error::Usage(format!("unknown format `{format}`, expected `ansi` or `html`"))),
+    }
+}
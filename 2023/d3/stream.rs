@@ -0,0 +1,170 @@
+use std::io::BufRead;
+
+use common::command::arg;
+use common::input::Source;
+use common::{Error, ParseError, Part};
+
+use super::BLANK_ROW;
+
+/// A number of a row, spanning the cells `start..end`.
+struct Number
+{
+    value: u32,
+    start: usize,
+    end: usize,
+    counted: bool,
+}
+
+impl Number
+{
+    fn is_adjacent(&self, x: usize) -> bool
+    {
+        self.start <= x + 1 && x <= self.end
+    }
+}
+
+#[derive(Default)]
+struct Row
+{
+    numbers: Vec<Number>,
+    symbols: Vec<(usize, char)>,
+}
+
+impl Row
+{
+    fn parse(line: &str) -> Result<Row, ParseError>
+    {
+        let mut res = Row::default();
+        let mut digits = None::<(usize, usize)>;
+
+        for (x, (i, c)) in line.char_indices().chain([(line.len(), '.')]).enumerate()
+        {
+            if c.is_ascii_digit()
+            {
+                digits.get_or_insert((x, i));
+                continue;
+            }
+
+            if let Some((start, offset)) = digits.take()
+            {
+                let text = &line[offset..i];
+                let value = text
+                    .parse()
+                    .map_err(|_| ParseError::new(line, text, "a number fitting in u32"))?;
+                res.numbers.push(Number { value, start, end: x, counted: false });
+            }
+            if c != '.'
+            {
+                res.symbols.push((x, c));
+            }
+        }
+
+        Ok(res)
+    }
+}
+
+/// Part 1 and part 2 of a schematic read one line at a time, only keeping
+/// the rows above and below the one being solved.
+pub fn solve<R: BufRead>(reader: R) -> Result<(u64, u64), Error>
+{
+    let mut window: [Row; 3] = Default::default();
+    let mut width = None;
+    let mut blank = None::<(usize, String)>;
+    let mut res = (0, 0);
+
+    for (y, line) in reader.lines().enumerate()
+    {
+        let line = line?;
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        let line = match y {
+            0 => line.strip_prefix('\u{feff}').unwrap_or(line),
+            _ => line,
+        };
+
+        // Blank lines are only allowed at the end.
+        if line.trim().is_empty()
+        {
+            blank.get_or_insert_with(|| (y + 1, line.to_string()));
+            continue;
+        }
+        if let Some((n, blank)) = blank
+        {
+            return Err(ParseError::new(&blank, &blank, BLANK_ROW).with_line(n).into());
+        }
+
+        let len = line.chars().count();
+        let width = *width.get_or_insert(len);
+        if len != width
+        {
+            let end = line.char_indices().nth(width).map_or(line.len(), |(i, _)| i);
+            let expected = format!("a line of {width} cells like the first one");
+            return Err(ParseError::new(line, &line[end..], expected).with_line(y + 1).into());
+        }
+
+        window.rotate_left(1);
+        window[2] = Row::parse(line).map_err(|e| e.with_line(y + 1))?;
+        solve_middle(&mut window, &mut res)?;
+    }
+
+    if width.is_none()
+    {
+        return Err(ParseError::new("", "", "a schematic line").with_line(1).into());
+    }
+
+    // The last row has nothing below it.
+    window.rotate_left(1);
+    window[2] = Row::default();
+    solve_middle(&mut window, &mut res)?;
+
+    Ok(res)
+}
+
+/// Counts the numbers touching the symbols of the middle row and adds up
+/// the ratios of its gears.
+fn solve_middle(window: &mut [Row; 3], res: &mut (u64, u64)) -> Result<(), Error>
+{
+    let overflow = |what: &str| Error::Solve(format!("the {what} overflows"));
+
+    let symbols = window[1].symbols.clone();
+
+    for &(x, _) in symbols.iter().filter(|(_, kind)| *kind == '*')
+    {
+        let parts = window
+            .iter()
+            .flat_map(|row| row.numbers.iter())
+            .filter(|n| n.is_adjacent(x))
+            .map(|n| u64::from(n.value))
+            .collect::<Vec<u64>>();
+
+        if parts.len() == 2
+        {
+            let ratio = parts[0].checked_mul(parts[1]).ok_or_else(|| overflow("ratio of a gear"))?;
+            res.1 = res.1.checked_add(ratio).ok_or_else(|| overflow("sum of the ratios"))?;
+        }
+    }
+
+    // The symbols of the middle row reach the numbers of all three rows.
+    for number in window.iter_mut().flat_map(|row| row.numbers.iter_mut())
+    {
+        if !number.counted && symbols.iter().any(|&(x, _)| number.is_adjacent(x))
+        {
+            number.counted = true;
+            res.0 = res.0.checked_add(u64::from(number.value)).ok_or_else(|| overflow("sum of the parts"))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// `stream <part>`: solves a schematic as it is read, so piped input of any
+/// height runs in constant memory.
+pub fn run(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let part = arg::<Part>(args, 0, "part")?;
+    let (part1, part2) = solve(source.open()?)?;
+
+    match part {
+        Part::One => Ok(part1.to_string()),
+        Part::Two => Ok(part2.to_string()),
+    }
+}
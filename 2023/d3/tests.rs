@@ -0,0 +1,109 @@
+use super::*;
+use common::input;
+
+const EXAMPLE: &str = include_str!("../tests/examples/3.txt");
+
+#[test]
+fn stream_matches_whole_schematic()
+{
+    let schematic = Day3::parse(EXAMPLE).unwrap();
+    let expected = (part1::solve(&schematic), part2::solve(&schematic).unwrap());
+
+    assert_eq!(stream::solve(EXAMPLE.as_bytes()).unwrap(), expected);
+    assert_eq!(stream::solve(EXAMPLE.replace('\n', "\r\n").as_bytes()).unwrap(), expected);
+}
+
+#[test]
+fn stream_rejects_ragged_and_empty_input()
+{
+    let error = stream::solve("12.\n..*\n.\n".as_bytes()).unwrap_err();
+    assert!(error.to_string().contains("expected a line of 3 cells"), "{error}");
+
+    assert!(stream::solve("".as_bytes()).is_err());
+}
+
+#[test]
+fn stream_agrees_with_whole_schematic()
+{
+    let whole = |text: &str| -> Result<(u64, u64), Error> {
+        let schematic = Day3::parse(&input::normalize(text))?;
+        Ok((part1::solve(&schematic), part2::solve(&schematic)?))
+    };
+
+    let texts = [
+        "12.\n   \n..*\n",
+        "12.\n\n..*\n",
+        "\n12.\n..*\n",
+        "12.\n..*\n\n \n",
+        "\u{feff}12.\r\n..*\r\n",
+        "12.\n..*\n.\n",
+        "12.\n..*",
+    ];
+    for text in texts
+    {
+        match (whole(text), stream::solve(text.as_bytes())) {
+            (Ok(a), Ok(b)) => assert_eq!(a, b, "{text:?}"),
+            (Err(a), Err(b)) => assert_eq!(a.to_string(), b.to_string(), "{text:?}"),
+            (a, b) => panic!("{text:?}: whole gives {a:?}, stream gives {b:?}"),
+        }
+    }
+
+    let error = stream::solve("12.\n   \n..*\n".as_bytes()).unwrap_err();
+    assert!(matches!(error, Error::Parse(e) if e.line == 2 && e.expected == BLANK_ROW));
+}
+
+#[test]
+fn symbols_know_their_parts()
+{
+    let schematic = Day3::parse(EXAMPLE).unwrap();
+
+    let first = &schematic.symbols()[0];
+    assert_eq!((first.kind, first.position), ('*', Point::new(3, 1)));
+    let parts = first.parts.iter().map(|&i| schematic.values()[i].value).collect::<Vec<u32>>();
+    assert_eq!(parts, vec![467, 35]);
+
+    let hashes = schematic.parts(|s| s.kind == '#');
+    assert_eq!(hashes.iter().map(|v| v.value).sum::<u32>(), 633);
+
+    let loose = schematic.loose().iter().map(|v| v.value).collect::<Vec<u32>>();
+    assert_eq!(loose, vec![114, 58]);
+
+    assert_eq!(schematic.gears('*', 1).map(|g| schematic.ratio(g).unwrap()).collect::<Vec<u64>>(), vec![617]);
+}
+
+#[test]
+fn only_stars_are_gears()
+{
+    let schematic = Day3::parse("2#3\n...\n4*5\n").unwrap();
+
+    assert_eq!(part1::solve(&schematic), 14);
+    assert_eq!(part2::solve(&schematic).unwrap(), 20);
+    assert_eq!(stream::solve("2#3\n...\n4*5\n".as_bytes()).unwrap(), (14, 20));
+}
+
+#[test]
+fn ratio_overflow_is_reported()
+{
+    let gear = "4000000000*4000000000";
+    let text = format!("{gear}\n{}\n{gear}\n", ".".repeat(gear.len()));
+    let schematic = Day3::parse(&text).unwrap();
+    assert!(matches!(part2::solve(&schematic), Err(Error::Solve(_))));
+    assert!(matches!(stream::solve(text.as_bytes()), Err(Error::Solve(_))));
+
+    let text = format!("{gear}\n..........4000000000.\n");
+    let schematic = Day3::parse(&text).unwrap();
+    assert!(matches!(schematic.ratios('*', 3), Err(Error::Solve(_))));
+}
+
+#[test]
+fn render_colours_numbers_by_status()
+{
+    let schematic = Day3::parse(EXAMPLE).unwrap();
+
+    let res = render::ansi(&schematic);
+    assert!(res.starts_with("\x1b[33m467\x1b[2m..\x1b[31m114"), "{res:?}");
+
+    let res = render::html(&schematic);
+    assert!(res.contains("<span class=\"part\">633</span>"), "{res}");
+    assert!(res.contains("<span class=\"gear-symbol\">*</span>"), "{res}");
+}
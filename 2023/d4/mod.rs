@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+use common::{input, Command, Error, ParseError, Solution};
+
+pub mod part1;
+pub mod part2;
+pub mod stream;
+
+#[cfg(test)]
+mod tests;
+
+/// Numbers below 128 live in a bitset, larger ones in a hash set that stays
+/// empty, and unallocated, for regular cards.
+#[derive(Debug, Default)]
+struct Numbers
+{
+    small: u128,
+    large: HashSet<u32>,
+}
+
+impl Numbers
+{
+    /// `false` if `value` was already there.
+    fn insert(&mut self, value: u32) -> bool
+    {
+        match value < u128::BITS {
+            true => {
+                let bit = 1u128 << value;
+                let res = self.small & bit == 0;
+                self.small |= bit;
+                res
+            },
+            false => self.large.insert(value),
+        }
+    }
+
+    fn common(&self, other: &Numbers) -> u32
+    {
+        let large = match self.large.is_empty() || other.large.is_empty() {
+            true => 0,
+            false => self.large.intersection(&other.large).count() as u32,
+        };
+
+        (self.small & other.small).count_ones() + large
+    }
+}
+
+#[derive(Debug)]
+pub struct Card
+{
+    id: u32,
+    winings: Numbers,
+    values: Numbers,
+}
+
+impl Card
+{
+    fn get_results(&self) -> u32
+    {
+        self.winings.common(&self.values)
+    }
+
+    /// One point for the first match, doubled by each further one.
+    fn points(&self) -> Result<u64, Error>
+    {
+        match self.get_results() {
+            0 => Ok(0),
+            n => 2_u64
+                .checked_pow(n - 1)
+                .ok_or_else(|| Error::Solve(format!("card {} is worth more than {} points", self.id, u64::MAX))),
+        }
+    }
+
+    /// `numbers` is a slice of `line`, kept around for error spans.
+    fn extract_numbers(line: &str, numbers: &str) -> Result<Numbers, ParseError>
+    {
+        let mut res = Numbers::default();
+        for v in numbers.split_whitespace()
+        {
+            let value = v.parse()
+                .map_err(|_| ParseError::new(line, v, "a number"))?;
+            if !res.insert(value)
+            {
+                return Err(ParseError::new(line, v, "a number not already listed"));
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn parse(line: &str) -> Result<Card, ParseError>
+    {
+        let rest = match line.strip_prefix("Card ") {
+            Some(v) => v,
+            None => {
+                let word = line.split(' ').next().unwrap_or(line);
+                return Err(ParseError::new(line, word, "`Card`"));
+            },
+        };
+
+        let (id, numbers) = match rest.split_once(':') {
+            Some(v) => v,
+            None => return Err(ParseError::new(line, &rest[rest.len()..], "`:`")),
+        };
+        let id: u32 = match id.trim().parse() {
+            Ok(val) => val,
+            Err(_) => return Err(ParseError::new(line, id.trim(), "a card number")),
+        };
+
+        let (winings, values) = match numbers.split_once('|') {
+            Some(v) => v,
+            None => return Err(ParseError::new(line, &numbers[numbers.len()..], "`|`")),
+        };
+        if let Some(index) = values.find('|')
+        {
+            return Err(ParseError::new(line, &values[index..index + 1], "a single `|`"));
+        }
+
+        Ok(Card {
+            id,
+            winings: Card::extract_numbers(line, winings)?,
+            values: Card::extract_numbers(line, values)?,
+        })
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4
+{
+    type Input = Vec<Card>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        Ok(input::parse_lines(input, Card::parse)?)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        part1::solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        part2::solve(input)
+    }
+}
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "stream",
+        usage: "<part>",
+        run: stream::run,
+    },
+];
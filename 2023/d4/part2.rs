@@ -1,148 +1,77 @@
-use std::cmp::PartialEq;
-use std::io::{self, BufRead, BufReader, Read};
-use std::fs;
-use std::env;
-use std::fmt;
 use std::collections::VecDeque;
-use std::collections::HashMap;
 
-#[derive(Debug)]
-struct Card
+use common::Error;
+
+use super::Card;
+
+/// Copies of the upcoming cards won so far, the front being the next card.
+/// It only ever holds as many entries as the most matches on one card.
+#[derive(Default)]
+pub struct Cascade
 {
-    id: u32,
-    winings: Vec<u32>,
-    values: Vec<u32>,
+    pending: VecDeque<u64>,
+    last_id: Option<u32>,
+    total: u64,
 }
 
-impl Card
+impl Cascade
 {
-    fn get_results(&self) -> u32
+    /// What was expected instead of card `id`, as cards must come in order
+    /// and without gaps.
+    pub fn unexpected(&self, id: u32) -> Option<String>
     {
-        self.values
-            .iter()
-            .filter(|v| self.winings.contains(&v))
-            .count() as u32
+        let last = self.last_id?;
+        match last.checked_add(1) {
+            Some(next) if next == id => None,
+            Some(next) => Some(format!("card {next}")),
+            None => Some(format!("no card after card {last}")),
+        }
     }
 
-    fn extract_numbers(line: &str) -> Vec<u32>
+    /// Counts every copy of `card` and hands them on to the next cards.
+    pub fn push(&mut self, card: &Card) -> Result<(), Error>
     {
-        line
-            .split(" ")
-            .filter_map(|v| match v.parse() {
-                Ok(v) => Some(v),
-                Err(_) => None,
-            })
-            .collect::<Vec<u32>>()
-    }
+        let overflow = || Error::Solve(format!("more than {} cards by card {}", u64::MAX, card.id));
 
-    fn parse(line: &str) -> Result<Card, &str>
-    {
-        if !line.starts_with("Card ")
-        {
-            return Err("Invalid line");
-        }
+        self.last_id = Some(card.id);
+        let copies = self.pending
+            .pop_front()
+            .unwrap_or(0)
+            .checked_add(1)
+            .ok_or_else(overflow)?;
+        self.total = self.total.checked_add(copies).ok_or_else(overflow)?;
 
-        let line = line.strip_prefix("Card ").unwrap();
-        let to_strip: usize;
-        let id: u32;
-        if let Some(index) = line.chars().position(|c| c == ':') {
-            id = match line
-                .chars()
-                .take(index)
-                .collect::<String>()
-                .trim()
-                .parse() {
-                Ok(val) => val,
-                Err(_) => return Err("Invalid card number"),
-            };
-            to_strip = index + 1;
-        }
-        else
+        for i in 0..card.get_results() as usize
         {
-            return Err("Missing colon");
+            match self.pending.get_mut(i) {
+                Some(v) => *v = v.checked_add(copies).ok_or_else(overflow)?,
+                None => self.pending.push_back(copies),
+            }
         }
 
-        let line_string = line.chars().skip(to_strip).collect::<String>();
-
-        let parts = line_string
-            .split("|")
-            .collect::<Vec<&str>>();
-        if parts.len() != 2
-        {
-            return Err("Invalid line");
-        }
+        Ok(())
+    }
 
-        Ok(Card {
-            id: id,
-            winings: Card::extract_numbers(parts[0]),
-            values: Card::extract_numbers(parts[1]),
-        })
+    /// Copies won past the last card are not counted, there is nothing to
+    /// copy.
+    pub fn total(&self) -> u64
+    {
+        self.total
     }
 }
 
-fn main() -> io::Result<()>
+pub fn solve(cards: &[Card]) -> Result<u64, Error>
 {
-    if env::args().len() != 2
+    let mut cascade = Cascade::default();
+
+    for (i, card) in cards.iter().enumerate()
     {
-        panic!("Usage: ./main <filename>");
+        if let Some(expected) = cascade.unexpected(card.id)
+        {
+            return Err(Error::Solve(format!("line {}: expected {expected}, found card {}", i + 1, card.id)));
+        }
+        cascade.push(card)?;
     }
 
-    let filename = env::args().nth_back(0).unwrap();
-
-    let file = fs::File::open(filename)?;
-
-    let mut reader = BufReader::new(file);
-
-    let lines = reader.lines()
-        .filter_map(|l| match l {
-            Err(_) => None,
-            Ok(v) => Some(v),
-        })
-        .collect::<Vec<String>>();
-
-    let cards = lines
-        .iter()
-        .filter_map(|l| match Card::parse(l.as_str()) {
-            Ok(v) => Some((v.id, v)),
-            Err(e) => {
-                println!("error on {l} => {e}");
-                None
-            },
-        })
-        .collect::<HashMap<u32, Card>>();
-
-    let mut values = HashMap::<u32, u32>::new();
-
-    let mut queue = VecDeque::<u32>::new();
-
-    let mut indices = cards
-        .keys()
-        .map(|&v| v)
-        .collect::<Vec<u32>>();
-
-    indices.sort_by(|a, b| b.cmp(a));
-    indices
-        .iter()
-        .map(|v| (v, &cards[v]))
-        .for_each(|(&i, card)| {
-            
-            let results = card.get_results();
-            let mut final_res: u32 = 1;
-            for i in 1..results + 1
-            {
-                final_res += values[&(card.id + i)];
-            }
-
-            values.insert(i, final_res);
-        });
-    println!("{values:?}");
-
-    let res = values.values()
-        .map(|&v| v)
-        .reduce(|a, b| a + b)
-        .unwrap_or(0);
-
-    println!("{res}");
-
-    Ok(())
+    Ok(cascade.total())
 }
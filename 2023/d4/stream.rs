@@ -0,0 +1,46 @@
+use std::io::BufRead;
+
+use common::command::arg;
+use common::input::{self, Source};
+use common::{Error, ParseError, Part};
+
+use super::part2::Cascade;
+use super::{part1, Card};
+
+/// Part 1 and part 2 of cards read one line at a time.
+pub fn solve<R: BufRead>(reader: R) -> Result<(u64, u64), Error>
+{
+    let mut points = 0u64;
+    let mut cascade = Cascade::default();
+
+    for line in input::stream_lines(reader)
+    {
+        let (n, line) = line?;
+        let card = Card::parse(&line).map_err(|e| e.with_line(n))?;
+
+        if let Some(expected) = cascade.unexpected(card.id)
+        {
+            let head = line.split_once(':').map_or(line.as_str(), |(head, _)| head);
+            let id = head.trim_start_matches("Card").trim();
+            return Err(ParseError::new(&line, id, expected).with_line(n).into());
+        }
+
+        points = part1::add(points, &card)?;
+        cascade.push(&card)?;
+    }
+
+    Ok((points, cascade.total()))
+}
+
+/// `stream <part>`: solves cards as they are read, so piped input of any
+/// length runs in constant memory.
+pub fn run(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let part = arg::<Part>(args, 0, "part")?;
+    let (part1, part2) = solve(source.open()?)?;
+
+    match part {
+        Part::One => Ok(part1.to_string()),
+        Part::Two => Ok(part2.to_string()),
+    }
+}
@@ -0,0 +1,67 @@
+use super::*;
+
+const EXAMPLE: &str = include_str!("../tests/examples/4.txt");
+
+#[test]
+fn matches_count_small_and_large_numbers()
+{
+    let card = Card::parse("Card 1: 5 127 128 9000 | 9000 5 7 128 126").unwrap();
+    assert_eq!(card.get_results(), 3);
+
+    let card = Card::parse("Card 2: 1 2 3 | 4 5 6").unwrap();
+    assert_eq!(card.get_results(), 0);
+}
+
+#[test]
+fn repeated_numbers_are_rejected()
+{
+    let error = Card::parse("Card 1: 3 4 | 8 300 300").unwrap_err();
+
+    assert_eq!(error.expected, "a number not already listed");
+    assert_eq!(error.columns, 20..23);
+}
+
+#[test]
+fn stream_matches_whole_file()
+{
+    let cards = Day4::parse(EXAMPLE).unwrap();
+
+    assert_eq!(part2::solve(&cards).unwrap(), 30);
+    assert_eq!(stream::solve(EXAMPLE.as_bytes()).unwrap(), (13, 30));
+}
+
+#[test]
+fn card_ids_must_follow_each_other()
+{
+    let text = "Card 1: 1 2 | 1 2\nCard 2: 1 | 1\nCard 4: 1 | 2\n";
+
+    let error = stream::solve(text.as_bytes()).unwrap_err();
+    assert!(error.to_string().starts_with("expected card 3, found `4`"), "{error}");
+
+    let cards = Day4::parse(text).unwrap();
+    assert!(part2::solve(&cards).is_err());
+
+    let text = "Card 4294967295: 1 | 2\nCard 3: 1 | 2\n";
+    let error = stream::solve(text.as_bytes()).unwrap_err();
+    assert!(error.to_string().starts_with("expected no card after card 4294967295, found `3`"), "{error}");
+    assert!(part2::solve(&Day4::parse(text).unwrap()).is_err());
+
+    // Copies won past the last card are dropped.
+    assert_eq!(stream::solve("Card 7: 1 2 | 1 2\nCard 8: 1 | 1\n".as_bytes()).unwrap(), (3, 3));
+}
+
+#[test]
+fn points_overflow_is_reported()
+{
+    let numbers = (1..=65).map(|n| n.to_string()).collect::<Vec<String>>().join(" ");
+    let text = format!("Card 1: {numbers} | {numbers}\n");
+
+    let cards = Day4::parse(&text).unwrap();
+    assert!(matches!(part1::solve(&cards), Err(Error::Solve(_))));
+    assert!(matches!(stream::solve(text.as_bytes()), Err(Error::Solve(_))));
+
+    let numbers = (1..=64).map(|n| n.to_string()).collect::<Vec<String>>().join(" ");
+    let text = format!("Card 1: {numbers} | {numbers}\nCard 2: {numbers} | {numbers}\n");
+    let cards = Day4::parse(&text).unwrap();
+    assert!(matches!(part1::solve(&cards), Err(Error::Solve(_))));
+}
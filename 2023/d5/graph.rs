@@ -0,0 +1,144 @@
+use common::input::Source;
+use common::{Error, Solution};
+
+use super::piecewise::Piecewise;
+use super::{Day5, Input, Int, Range};
+
+const WIDTH: f64 = 960.0;
+const MARGIN: f64 = 120.0;
+const BAND: f64 = 24.0;
+const SPACING: f64 = 72.0;
+
+const COLORS: [&str; 6] = ["#4e79a7", "#f28e2b", "#e15759", "#76b7b2", "#59a14f", "#edc948"];
+
+/// Escapes `value` for SVG text, Graphviz reads the same entities.
+fn escape(value: &str) -> String
+{
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The categories as nodes and every map as an edge labelled with its
+/// lines, one `source -> destination` range per line.
+pub fn dot<T: Int>(input: &Input<T>) -> String
+{
+    let mut res = vec![
+        "digraph almanac {".to_string(),
+        "    rankdir=LR;".to_string(),
+        "    node [shape=box];".to_string(),
+    ];
+
+    let mut categories = Vec::<&str>::new();
+    for map in &input.maps
+    {
+        for category in [map.source.as_str(), map.destination.as_str()]
+        {
+            if !categories.contains(&category)
+            {
+                categories.push(category);
+                res.push(format!("    \"{}\";", escape(category)));
+            }
+        }
+    }
+
+    for map in &input.maps
+    {
+        let label = map.ranges
+            .iter()
+            .map(|line| match (line.source(), line.destination()) {
+                (Some(s), Some(d)) => format!("{s} -> {d}\\l"),
+                _ => format!("`{line}` is empty\\l"),
+            })
+            .collect::<String>();
+        res.push(format!("    \"{}\" -> \"{}\" [label=\"{label}\"];", escape(&map.source), escape(&map.destination)));
+    }
+
+    res.push("}".to_string());
+    res.join("\n")
+}
+
+/// One band per category along the chain from `from` to `to`, each source
+/// interval drawn as a polygon into where it lands in the next band. Values
+/// no line covers fall straight down in grey.
+pub fn svg<T: Int>(input: &Input<T>, from: &str, to: &str) -> Result<String, Error>
+{
+    let chain = input.chain(from, to)?;
+    let functions = chain
+        .iter()
+        .map(|map| Piecewise::from_map(map))
+        .collect::<Vec<Piecewise<T>>>();
+
+    let values = functions
+        .iter()
+        .flat_map(|f| f.segments())
+        .flat_map(|s| [s.source.start, s.source.end, s.destination().start, s.destination().end])
+        .collect::<Vec<T>>();
+    let axis = match (values.iter().min(), values.iter().max()) {
+        (Some(&lo), Some(&hi)) if lo < hi => Range { start: lo.min(T::ZERO), end: hi },
+        _ => Range { start: T::ZERO, end: T::ONE },
+    };
+
+    let scale = (WIDTH - MARGIN - 10.0) / (axis.end.as_f64() - axis.start.as_f64());
+    let x = |v: T| MARGIN + (v.as_f64() - axis.start.as_f64()) * scale;
+    let top = |band: usize| 10.0 + band as f64 * (BAND + SPACING);
+    let height = top(chain.len()) + BAND + 10.0;
+
+    let mut res = vec![
+        format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\" font-family=\"monospace\" font-size=\"12\">"),
+    ];
+
+    let mut categories = vec![from];
+    categories.extend(chain.iter().map(|map| map.destination.as_str()));
+    for (band, category) in categories.iter().enumerate()
+    {
+        let y = top(band);
+        res.push(format!("<text x=\"4\" y=\"{}\">{}</text>", y + BAND / 2.0 + 4.0, escape(category)));
+        res.push(format!("<rect x=\"{MARGIN}\" y=\"{y}\" width=\"{}\" height=\"{BAND}\" fill=\"#eeeeee\"/>", WIDTH - MARGIN - 10.0));
+    }
+
+    let polygon = |band: usize, source: Range<T>, destination: Range<T>, color: &str, title: String| {
+        let (y0, y1) = (top(band) + BAND, top(band + 1));
+        format!(
+            "<polygon points=\"{:.1},{y0} {:.1},{y0} {:.1},{y1} {:.1},{y1}\" fill=\"{color}\" fill-opacity=\"0.6\"><title>{}</title></polygon>",
+            x(source.start), x(source.end), x(destination.end), x(destination.start), escape(&title)
+        )
+    };
+
+    for (band, function) in functions.iter().enumerate()
+    {
+        let mut identity = vec![axis];
+        for (i, segment) in function.segments().iter().enumerate()
+        {
+            identity = identity.iter().flat_map(|r| r.subtract(&segment.source)).collect();
+            res.push(polygon(band, segment.source, segment.destination(), COLORS[i % COLORS.len()], segment.to_string()));
+        }
+        for range in identity
+        {
+            res.push(polygon(band, range, range, "#999999", format!("{range} -> identity")));
+        }
+    }
+
+    res.push("</svg>".to_string());
+    Ok(res.join("\n"))
+}
+
+/// `dot`: the whole almanac as a Graphviz graph.
+pub fn dot_command(source: &Source, _args: &[String]) -> Result<String, Error>
+{
+    let input = Day5::parse(&source.load()?)?;
+    Ok(dot(&input))
+}
+
+/// `svg [from] [to]`: the bands of the chain, from seeds to locations by
+/// default.
+pub fn svg_command(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let input = Day5::parse(&source.load()?)?;
+    let from = args.first().map(|s| s.as_str()).unwrap_or("seed");
+    let to = args.get(1).map(|s| s.as_str()).unwrap_or("location");
+
+    svg(&input, from, to)
+}
@@ -0,0 +1,155 @@
+use common::Error;
+
+use super::lint::overlaps;
+use super::{Input, InputLine, Int, Map, Range};
+
+impl<T: Int> Map<T>
+{
+    fn name(&self) -> String
+    {
+        format!("{}-to-{}", self.source, self.destination)
+    }
+
+    /// Every value this map sends to `value`, in increasing order.
+    fn preimages(&self, value: T) -> Vec<T>
+    {
+        let mut res = Vec::<T>::new();
+
+        for line in &self.ranges
+        {
+            let covered = line.destination().is_some_and(|r| r.contains(value));
+            if !covered
+            {
+                continue;
+            }
+
+            // In range by construction of the line.
+            let offset = value.checked_sub(line.destination_start).unwrap();
+            let source = line.source_start.checked_add(offset).unwrap();
+            if self.get_value(source) == value
+            {
+                res.push(source);
+            }
+        }
+
+        if self.get_value(value) == value
+        {
+            res.push(value);
+        }
+
+        res.sort();
+        res.dedup();
+        res
+    }
+
+    /// Reports lines whose sources overlap, so that only the first one is
+    /// ever used, and values reachable from two different sources.
+    fn check_invertible(&self) -> Result<(), Error>
+    {
+        let lines = &self.ranges;
+        let name = self.name();
+
+        if let Some((a, b, overlap)) = overlaps(lines, InputLine::source).first()
+        {
+            return Err(Error::Solve(format!("{name} map: lines `{a}` and `{b}` overlap on sources {overlap}")));
+        }
+        if let Some((a, b, overlap)) = overlaps(lines, InputLine::destination).first()
+        {
+            return Err(Error::Solve(format!("{name} map: lines `{a}` and `{b}` both produce {overlap}")));
+        }
+
+        // Values no line covers map to themselves and must not collide with
+        // a line's destination.
+        for line in lines
+        {
+            let mut uncovered = line.destination().into_iter().collect::<Vec<Range<T>>>();
+            for other in lines.iter().filter_map(|l| l.source())
+            {
+                uncovered = uncovered
+                    .iter()
+                    .flat_map(|r| r.subtract(&other))
+                    .collect();
+            }
+
+            if let Some(r) = uncovered.first()
+            {
+                return Err(Error::Solve(format!("{name} map: line `{line}` produces {r}, which is also left unmapped")));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Every value going through all of `chain` to become `value`.
+pub(super) fn invert_chain<T: Int>(chain: &[&Map<T>], value: T) -> Vec<T>
+{
+    let mut values = vec![value];
+
+    for map in chain.iter().rev()
+    {
+        values = values
+            .iter()
+            .flat_map(|&v| map.preimages(v))
+            .collect();
+        values.sort();
+        values.dedup();
+    }
+
+    values
+}
+
+impl<T: Int> Input<T>
+{
+    /// Every `from` value that converts to the `to` value `value`.
+    pub fn invert(&self, from: &str, to: &str, value: T) -> Result<Vec<T>, Error>
+    {
+        Ok(invert_chain(&self.chain(from, to)?, value))
+    }
+
+    /// The single `from` value converting to `value`, if any; several
+    /// candidates are reported instead of picking one.
+    pub fn inverse(&self, from: &str, to: &str, value: T) -> Result<Option<T>, Error>
+    {
+        let values = self.invert(from, to, value)?;
+
+        match values.len() {
+            0 => Ok(None),
+            1 => Ok(Some(values[0])),
+            _ => {
+                let values = values
+                    .iter()
+                    .map(|v| v.to_string())
+                    .collect::<Vec<String>>();
+                Err(Error::Solve(format!("{to} {value} is produced by several {from} values: {}", values.join(", "))))
+            },
+        }
+    }
+
+    /// Checks that every map between `from` and `to` is one-to-one.
+    pub fn check_invertible(&self, from: &str, to: &str) -> Result<(), Error>
+    {
+        for map in self.chain(from, to)?
+        {
+            map.check_invertible()?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that each of `values` comes back unchanged after a conversion
+    /// from `from` to `to` and back.
+    pub fn check_round_trip(&self, from: &str, to: &str, values: &[T]) -> Result<(), Error>
+    {
+        for &value in values
+        {
+            let converted = self.convert(from, to, value)?;
+            if self.inverse(from, to, converted)? != Some(value)
+            {
+                return Err(Error::Solve(format!("{from} {value} converts to {to} {converted}, which does not invert back")));
+            }
+        }
+
+        Ok(())
+    }
+}
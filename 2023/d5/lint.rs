@@ -0,0 +1,172 @@
+use std::fmt;
+
+use common::input::Source;
+use common::Error;
+
+use super::{Input, InputLine, Int, Map, Range};
+
+/// Pairs of lines whose ranges, as picked by `range`, intersect.
+pub(super) fn overlaps<T: Int>(lines: &[InputLine<T>], range: fn(&InputLine<T>) -> Option<Range<T>>)
+    -> Vec<(&InputLine<T>, &InputLine<T>, Range<T>)>
+{
+    let mut res = Vec::new();
+
+    for (i, a) in lines.iter().enumerate()
+    {
+        for b in lines.iter().skip(i + 1)
+        {
+            if let (Some(x), Some(y)) = (range(a), range(b))
+            {
+                if let Some(overlap) = x.intersect(&y)
+                {
+                    res.push((a, b, overlap));
+                }
+            }
+        }
+    }
+
+    res
+}
+
+/// Sorted, merged union of `ranges`.
+fn union<T: Int>(mut ranges: Vec<Range<T>>) -> Vec<Range<T>>
+{
+    ranges.sort_by_key(|r| r.start);
+
+    let mut res = Vec::<Range<T>>::new();
+    for range in ranges
+    {
+        match res.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => res.push(range),
+        }
+    }
+
+    res
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Issue<T>
+{
+    ZeroLength(String),
+    OverlappingSources(String, String, Range<T>),
+    OverlappingDestinations(String, String, Range<T>),
+    /// Values between two lines, which map to themselves.
+    Gap(Range<T>),
+}
+
+impl<T: Int> fmt::Display for Issue<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self {
+            Issue::ZeroLength(line) => write!(f, "line `{line}` has a zero length"),
+            Issue::OverlappingSources(a, b, r) => write!(f, "lines `{a}` and `{b}` overlap on sources {r}, only the first applies"),
+            Issue::OverlappingDestinations(a, b, r) => write!(f, "lines `{a}` and `{b}` both produce {r}"),
+            Issue::Gap(r) => write!(f, "no line covers {r}, it maps to itself"),
+        }
+    }
+}
+
+/// What one map covers and what is wrong with it.
+#[derive(Debug)]
+pub struct Coverage<T>
+{
+    pub name: String,
+    pub lines: usize,
+    /// From the lowest to the highest source value of any line.
+    pub span: Option<Range<T>>,
+    pub covered: T,
+    pub issues: Vec<Issue<T>>,
+}
+
+impl<T: Int> Coverage<T>
+{
+    fn new(map: &Map<T>) -> Coverage<T>
+    {
+        let mut issues = Vec::<Issue<T>>::new();
+
+        for line in map.ranges.iter().filter(|l| l.source().is_none())
+        {
+            issues.push(Issue::ZeroLength(line.to_string()));
+        }
+        for (a, b, r) in overlaps(&map.ranges, InputLine::source)
+        {
+            issues.push(Issue::OverlappingSources(a.to_string(), b.to_string(), r));
+        }
+        for (a, b, r) in overlaps(&map.ranges, InputLine::destination)
+        {
+            issues.push(Issue::OverlappingDestinations(a.to_string(), b.to_string(), r));
+        }
+
+        let sources = union(map.ranges.iter().filter_map(|l| l.source()).collect());
+        for pair in sources.windows(2)
+        {
+            issues.push(Issue::Gap(Range { start: pair[0].end, end: pair[1].start }));
+        }
+
+        // The union is disjoint and within `T`, so neither can overflow.
+        let covered = sources
+            .iter()
+            .fold(T::ZERO, |acc, r| acc.checked_add(r.end.checked_sub(r.start).unwrap()).unwrap());
+        let span = match (sources.first(), sources.last()) {
+            (Some(first), Some(last)) => Some(Range { start: first.start, end: last.end }),
+            _ => None,
+        };
+
+        Coverage {
+            name: format!("{}-to-{}", map.source, map.destination),
+            lines: map.ranges.len(),
+            span,
+            covered,
+            issues,
+        }
+    }
+}
+
+impl<T: Int> fmt::Display for Coverage<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match &self.span {
+            Some(span) => write!(f, "{}: {} lines, sources {span}, {} values covered", self.name, self.lines, self.covered)?,
+            None => write!(f, "{}: {} lines, nothing covered", self.name, self.lines)?,
+        }
+
+        for issue in &self.issues
+        {
+            write!(f, "\n  warning: {issue}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// One coverage report per map, in file order.
+pub fn lint<T: Int>(input: &Input<T>) -> Vec<Coverage<T>>
+{
+    input.maps
+        .iter()
+        .map(Coverage::new)
+        .collect()
+}
+
+/// `lint`: prints the coverage of every map, failing when any has issues.
+pub fn run(source: &Source, _args: &[String]) -> Result<String, Error>
+{
+    // Not `Day5::parse`, which would print the warnings a second time.
+    let input = Input::<u64>::parse(&source.load()?)?;
+    let reports = lint(&input);
+
+    let res = reports
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    let issues = reports.iter().map(|r| r.issues.len()).sum::<usize>();
+    match issues {
+        0 => Ok(res),
+        n => Err(Error::Solve(format!("{res}\n{n} issue(s) found"))),
+    }
+}
@@ -0,0 +1,475 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::str::FromStr;
+
+use common::{input, Command, Error, ParseError, Solution};
+
+pub mod graph;
+mod inverse;
+pub mod lint;
+pub mod part1;
+pub mod part2;
+pub mod piecewise;
+pub mod trace;
+
+#[cfg(test)]
+mod tests;
+
+/// Integer type an almanac can be read into.
+pub trait Int: Copy + Ord + Hash + fmt::Debug + fmt::Display + FromStr + Send + Sync
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_sub(self, other: Self) -> Option<Self>;
+
+    /// Lossy, only meant for drawing.
+    fn as_f64(self) -> f64;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(
+            impl Int for $t
+            {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, other: Self) -> Option<Self>
+                {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self>
+                {
+                    <$t>::checked_sub(self, other)
+                }
+
+                fn as_f64(self) -> f64
+                {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(u32, u64, u128, i32, i64, i128);
+
+fn type_name<T>() -> &'static str
+{
+    std::any::type_name::<T>()
+}
+
+/// Half-open interval `[start, end)` of values of one category.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Range<T>
+{
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Int> Range<T>
+{
+    pub fn new(start: T, end: T) -> Option<Range<T>>
+    {
+        match start < end {
+            true => Some(Range { start, end }),
+            false => None,
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool
+    {
+        self.start <= value && value < self.end
+    }
+
+    pub fn intersect(&self, other: &Range<T>) -> Option<Range<T>>
+    {
+        Range::new(self.start.max(other.start), self.end.min(other.end))
+    }
+
+    /// The parts of `self` before and after `other`.
+    pub fn subtract(&self, other: &Range<T>) -> Vec<Range<T>>
+    {
+        [
+            Range::new(self.start, self.end.min(other.start)),
+            Range::new(self.start.max(other.end), self.end),
+        ]
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+impl<T: Int> fmt::Display for Range<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+#[derive(Debug)]
+struct InputLine<T>
+{
+    source_start: T,
+    destination_start: T,
+    len: T,
+}
+
+impl<T: Int> fmt::Display for InputLine<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{} {} {}", self.destination_start, self.source_start, self.len)
+    }
+}
+
+type InputRange<T> = Vec<InputLine<T>>;
+
+/// The first line covering `seed`, which is the one mapping it.
+fn input_range_find_line<T: Int>(range: &InputRange<T>, seed: T) -> Option<&InputLine<T>>
+{
+    range
+        .iter()
+        .find(|line| line.find_value(seed).is_some())
+}
+
+fn input_range_get_value<T: Int>(range: &InputRange<T>, seed: T) -> T
+{
+    match input_range_find_line(range, seed) {
+        Some(line) => line.find_value(seed).unwrap(),
+        None => seed,
+    }
+}
+
+impl<T: Int> InputLine<T>
+{
+    /// Rejects lines whose source or destination range would not fit in `T`,
+    /// so that mapping a value can never overflow afterwards.
+    fn parse(line: &str) -> Result<Self, ParseError>
+    {
+        let parts = line
+            .split_whitespace()
+            .collect::<Vec<&str>>();
+        if parts.len() != 3
+        {
+            return Err(ParseError::new(line, line, "`<destination> <source> <length>`"));
+        }
+
+        let values = parts
+            .iter()
+            .map(|v| v.parse()
+                .map_err(|_| ParseError::new(line, v, format!("a number fitting in {}", type_name::<T>()))))
+            .collect::<Result<Vec<T>, ParseError>>()?;
+
+        let res = InputLine {
+            destination_start: values[0],
+            source_start: values[1],
+            len: values[2],
+        };
+
+        if res.len < T::ZERO
+        {
+            return Err(ParseError::new(line, parts[2], "a non-negative length"));
+        }
+        if res.source_start.checked_add(res.len).is_none()
+            || res.destination_start.checked_add(res.len).is_none()
+        {
+            let expected = format!("a mapping whose ranges end within {}", type_name::<T>());
+            return Err(ParseError::new(line, line, expected));
+        }
+
+        Ok(res)
+    }
+
+    fn source_end(&self) -> T
+    {
+        self.source_start.checked_add(self.len).unwrap()
+    }
+
+    fn destination_end(&self) -> T
+    {
+        self.destination_start.checked_add(self.len).unwrap()
+    }
+
+    fn source(&self) -> Option<Range<T>>
+    {
+        Range::new(self.source_start, self.source_end())
+    }
+
+    fn destination(&self) -> Option<Range<T>>
+    {
+        Range::new(self.destination_start, self.destination_end())
+    }
+
+    fn find_value(&self, value: T) -> Option<T>
+    {
+        if value >= self.source_start
+        {
+            let offset = value.checked_sub(self.source_start)?;
+            if offset < self.len {
+                return self.destination_start.checked_add(offset);
+            }
+        }
+
+        None
+    }
+}
+
+/// One `<source>-to-<destination> map:` block.
+#[derive(Debug)]
+struct Map<T>
+{
+    source: String,
+    destination: String,
+    ranges: InputRange<T>,
+}
+
+impl<T: Int> Map<T>
+{
+    fn parse_header(line: &str) -> Result<(&str, &str), ParseError>
+    {
+        let name = match line.trim_end().strip_suffix(" map:") {
+            Some(v) => v,
+            None => return Err(ParseError::new(line, line, "`<category>-to-<category> map:`")),
+        };
+
+        match name.split_once("-to-") {
+            Some((source, destination)) if !source.is_empty() && !destination.is_empty() => {
+                Ok((source, destination))
+            },
+            _ => Err(ParseError::new(line, name, "`<category>-to-<category>`")),
+        }
+    }
+
+    fn get_value(&self, value: T) -> T
+    {
+        input_range_get_value(&self.ranges, value)
+    }
+}
+
+#[derive(Debug)]
+pub struct Input<T>
+{
+    seeds: Vec<T>,
+    maps: Vec<Map<T>>,
+}
+
+impl<T: Int> Input<T>
+{
+    fn new() -> Self
+    {
+        Input {
+            seeds: Vec::<T>::new(),
+            maps: Vec::<Map<T>>::new(),
+        }
+    }
+
+    /// `values` are the lines of one map block, all slices of `input`.
+    fn collect_line(&mut self, input: &str, values: &[&str]) -> Result<(), ParseError>
+    {
+        let key = values[0];
+        let (source, destination) = Map::<T>::parse_header(key)
+            .map_err(|e| e.with_line(input::line_number(input, key)))?;
+        let inputs = values
+            .iter()
+            .skip(1)
+            .map(|v| InputLine::parse(v)
+                .map_err(|e| e.with_line(input::line_number(input, v))))
+            .collect::<Result<Vec<InputLine<T>>, ParseError>>()?;
+
+        self.maps.push(Map {
+            source: source.to_string(),
+            destination: destination.to_string(),
+            ranges: inputs,
+        });
+
+        Ok(())
+    }
+
+    /// Every way of going from `category` to `to` without visiting a
+    /// category twice, as indices into `maps`.
+    fn find_paths(&self, category: &str, to: &str, path: &mut Vec<usize>, res: &mut Vec<Vec<usize>>)
+    {
+        if category == to
+        {
+            res.push(path.clone());
+            return;
+        }
+
+        for (i, map) in self.maps.iter().enumerate()
+        {
+            let visited = path.iter().any(|&j| self.maps[j].source == map.destination);
+            if map.source != category || visited || map.destination == map.source
+            {
+                continue;
+            }
+
+            path.push(i);
+            self.find_paths(&map.destination, to, path, res);
+            path.pop();
+        }
+    }
+
+    fn describe_path(&self, from: &str, path: &[usize]) -> String
+    {
+        let mut res = from.to_string();
+        for &i in path
+        {
+            res += &format!(" -> {}", self.maps[i].destination);
+        }
+
+        res
+    }
+
+    /// The maps a `from` value goes through to become a `to` value, which
+    /// must be reachable in exactly one way.
+    fn chain(&self, from: &str, to: &str) -> Result<Vec<&Map<T>>, Error>
+    {
+        let mut paths = Vec::<Vec<usize>>::new();
+        self.find_paths(from, to, &mut Vec::new(), &mut paths);
+
+        match paths.len() {
+            0 => Err(Error::Solve(format!("no chain of maps from `{from}` to `{to}`"))),
+            1 => Ok(paths[0].iter().map(|&i| &self.maps[i]).collect()),
+            _ => {
+                let paths = paths
+                    .iter()
+                    .map(|p| self.describe_path(from, p))
+                    .collect::<Vec<String>>();
+                Err(Error::Solve(format!("ambiguous chain from `{from}` to `{to}`: {}", paths.join(", "))))
+            },
+        }
+    }
+
+    /// Converts a `from` value into a `to` value.
+    pub fn convert(&self, from: &str, to: &str, value: T) -> Result<T, Error>
+    {
+        Ok(self.chain(from, to)?
+            .iter()
+            .fold(value, |value, map| map.get_value(value)))
+    }
+
+    fn build_location_map(&self) -> Result<HashMap<T, T>, Error>
+    {
+        let locations = self.piecewise("seed", "location")?;
+        let mut res = HashMap::<T, T>::new();
+
+        for seed in &self.seeds {
+            res.insert(*seed, locations.get(*seed));
+        }
+
+        Ok(res)
+    }
+
+    fn parse(input: &str) -> Result<Input<T>, ParseError>
+    {
+        let mut blocks = input::blocks(input);
+        let mut lines = match blocks.next() {
+            Some(v) => input::lines(v),
+            None => return Err(ParseError::new("", "", "`seeds:`").with_line(1)),
+        };
+        let line = lines.next().unwrap();
+        let line_number = input::line_number(input, line);
+
+        let seeds = match line.strip_prefix("seeds:") {
+            Some(v) => v,
+            None => {
+                let word = line.split(' ').next().unwrap_or(line);
+                return Err(ParseError::new(line, word, "`seeds:`").with_line(line_number));
+            },
+        };
+        if let Some(extra) = lines.next()
+        {
+            let error = ParseError::new(extra, extra, "a blank line after the seeds");
+            return Err(error.with_line(line_number + 1));
+        }
+
+        let mut res = Input::new();
+
+        let expected = format!("a seed number fitting in {}", type_name::<T>());
+        res.seeds = seeds
+            .split_whitespace()
+            .map(|v| v.parse()
+                .map_err(|_| ParseError::new(line, v, expected.as_str()).with_line(line_number)))
+            .collect::<Result<Vec<T>, ParseError>>()?;
+
+        for block in blocks
+        {
+            res.collect_line(input, &input::lines(block).collect::<Vec<&str>>())?;
+        }
+
+        Ok(res)
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5
+{
+    type Input = Input<u64>;
+    type Output = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        let res = Input::parse(input)?;
+
+        if cfg!(debug_assertions)
+        {
+            for report in lint::lint(&res).iter().filter(|r| !r.issues.is_empty())
+            {
+                eprintln!("{report}");
+            }
+        }
+
+        Ok(res)
+    }
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        part1::solve(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Error>
+    {
+        part2::solve(input)
+    }
+}
+
+pub const COMMANDS: &[Command] = &[
+    Command {
+        name: "table",
+        usage: "[from] [to]",
+        run: piecewise::table,
+    },
+    Command {
+        name: "dot",
+        usage: "",
+        run: graph::dot_command,
+    },
+    Command {
+        name: "svg",
+        usage: "[from] [to]",
+        run: graph::svg_command,
+    },
+    Command {
+        name: "crosscheck",
+        usage: "",
+        run: part2::crosscheck,
+    },
+    Command {
+        name: "lint",
+        usage: "",
+        run: lint::run,
+    },
+    Command {
+        name: "explain",
+        usage: "<part> <seed>",
+        run: trace::explain,
+    },
+];
@@ -0,0 +1,240 @@
+use std::sync::Mutex;
+use std::thread;
+
+use common::input::Source;
+use common::{Error, Solution};
+
+use super::inverse::invert_chain;
+use super::{input_range_get_value, type_name, Day5, Input, InputLine, InputRange, Int, Range};
+
+/// A piece of a range and where one map sends it.
+pub(super) struct Split<'a, T>
+{
+    pub from: Range<T>,
+    pub to: Range<T>,
+    /// `None` when no line covers the piece, which then maps to itself.
+    pub line: Option<&'a InputLine<T>>,
+}
+
+/// Pushes whole ranges through one map, splitting them at line boundaries.
+/// Each piece is mapped by the first line covering it, like
+/// `input_range_get_value`, and whatever no line covers maps to itself.
+pub(super) fn split_ranges<T: Int>(range: &InputRange<T>, ranges: Vec<Range<T>>) -> Vec<Split<'_, T>>
+{
+    let mut pending = ranges;
+    let mut res = Vec::<Split<T>>::new();
+
+    for line in range
+    {
+        let source = match line.source() {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let mut unmatched = Vec::<Range<T>>::new();
+        for r in pending
+        {
+            if let Some(inside) = r.intersect(&source)
+            {
+                // Both ends lie within the line, which was checked to fit in `T`.
+                let start = line.find_value(inside.start).unwrap();
+                let len = inside.end.checked_sub(inside.start).unwrap();
+                res.push(Split {
+                    from: inside,
+                    to: Range {
+                        start,
+                        end: start.checked_add(len).unwrap(),
+                    },
+                    line: Some(line),
+                });
+            }
+
+            unmatched.extend(r.subtract(&source));
+        }
+
+        pending = unmatched;
+    }
+
+    res.extend(pending.into_iter().map(|r| Split { from: r, to: r, line: None }));
+    res
+}
+
+fn map_ranges<T: Int>(range: &InputRange<T>, ranges: Vec<Range<T>>) -> Vec<Range<T>>
+{
+    split_ranges(range, ranges)
+        .into_iter()
+        .map(|split| split.to)
+        .collect()
+}
+
+pub(super) fn seed_ranges<T: Int>(input: &Input<T>) -> Result<Vec<Range<T>>, Error>
+{
+    if !input.seeds.len().is_multiple_of(2)
+    {
+        let count = input.seeds.len();
+        return Err(Error::Solve(format!("seeds must come in (start, length) pairs, found {count} values")));
+    }
+
+    let mut ranges = Vec::<Range<T>>::new();
+    for pair in input.seeds.chunks(2)
+    {
+        let (start, len) = (pair[0], pair[1]);
+        let end = match start.checked_add(len) {
+            Some(v) if len >= T::ZERO => v,
+            _ => {
+                let name = type_name::<T>();
+                return Err(Error::Solve(format!("seed range {start} {len} does not fit in {name}")));
+            },
+        };
+
+        if let Some(range) = Range::new(start, end)
+        {
+            ranges.push(range);
+        }
+    }
+
+    Ok(ranges)
+}
+
+pub fn solve<T: Int>(input: &Input<T>) -> Result<T, Error>
+{
+    let ranges = seed_ranges(input)?;
+
+    let locations = input.chain("seed", "location")?
+        .iter()
+        .fold(ranges, |ranges, map| map_ranges(&map.ranges, ranges));
+
+    locations
+        .iter()
+        .map(|r| r.start)
+        .min()
+        .ok_or_else(|| Error::Solve("no seeds".to_string()))
+}
+
+/// Walks locations upward and stops at the first one that some seed of the
+/// seed ranges converts to.
+pub fn solve_by_inverse<T: Int>(input: &Input<T>) -> Result<T, Error>
+{
+    let ranges = seed_ranges(input)?;
+    let chain = input.chain("seed", "location")?;
+
+    if ranges.is_empty()
+    {
+        return Err(Error::Solve("no seeds".to_string()));
+    }
+
+    // A location is either a seed no line moved or lies in a destination.
+    let destinations = chain
+        .iter()
+        .flat_map(|map| map.ranges.iter())
+        .map(|line| line.destination_start);
+    let mut location = ranges
+        .iter()
+        .map(|r| r.start)
+        .chain(destinations)
+        .min()
+        .unwrap();
+    loop
+    {
+        let seeds = invert_chain(&chain, location);
+        if seeds.iter().any(|&seed| ranges.iter().any(|r| r.contains(seed)))
+        {
+            return Ok(location);
+        }
+
+        location = match location.checked_add(T::ONE) {
+            Some(v) => v,
+            None => return Err(Error::Solve("no location is reachable from the seed ranges".to_string())),
+        };
+    }
+}
+
+/// How many seeds a brute force worker takes at once, or less when `T`
+/// cannot hold that many.
+fn chunk_size<T: Int>() -> T
+{
+    let mut res = T::ONE;
+    for _ in 0..16
+    {
+        res = res.checked_add(res).unwrap_or(res);
+    }
+
+    res
+}
+
+/// Takes the next chunk off the front of the remaining seed ranges.
+fn next_chunk<T: Int>(ranges: &Mutex<Vec<Range<T>>>, size: T) -> Option<Range<T>>
+{
+    let mut ranges = ranges.lock().unwrap();
+    let range = ranges.pop()?;
+
+    let end = match range.start.checked_add(size) {
+        Some(v) if v < range.end => v,
+        _ => range.end,
+    };
+    if let Some(rest) = Range::new(end, range.end)
+    {
+        ranges.push(rest);
+    }
+
+    Some(Range { start: range.start, end })
+}
+
+/// Converts every single seed of the seed ranges, spread over all cores.
+/// Slow, but simple enough to serve as the reference for `solve`.
+pub fn solve_brute_force<T: Int>(input: &Input<T>) -> Result<T, Error>
+{
+    let ranges = Mutex::new(seed_ranges(input)?);
+    let chain = input.chain("seed", "location")?;
+    let size = chunk_size::<T>();
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+
+    let worker = || {
+        let mut res = None::<T>;
+        while let Some(chunk) = next_chunk(&ranges, size)
+        {
+            let mut seed = chunk.start;
+            while seed < chunk.end
+            {
+                let location = chain
+                    .iter()
+                    .fold(seed, |value, map| input_range_get_value(&map.ranges, value));
+                res = Some(res.map_or(location, |v| v.min(location)));
+                seed = seed.checked_add(T::ONE).unwrap();
+            }
+        }
+
+        res
+    };
+
+    let res = thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| scope.spawn(worker))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .filter_map(|h| h.join().unwrap())
+            .min()
+    });
+
+    res.ok_or_else(|| Error::Solve("no seeds".to_string()))
+}
+
+/// `crosscheck`: solves part 2 by splitting intervals, by inverting the maps
+/// and by brute force, failing when they disagree.
+pub fn crosscheck(source: &Source, _args: &[String]) -> Result<String, Error>
+{
+    let input = Day5::parse(&source.load()?)?;
+
+    let intervals = solve(&input)?;
+    let inverse = solve_by_inverse(&input)?;
+    let brute_force = solve_brute_force(&input)?;
+    if intervals != inverse || intervals != brute_force
+    {
+        return Err(Error::Solve(format!(
+            "strategies disagree: intervals give {intervals}, inverse gives {inverse}, brute force gives {brute_force}"
+        )));
+    }
+
+    Ok(format!("all strategies give {intervals}"))
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+
+use common::input::Source;
+use common::{Error, Solution};
+
+use super::{Day5, Input, Int, Map, Range};
+
+/// Values in `source` move to `destination_start` onwards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Segment<T>
+{
+    pub source: Range<T>,
+    pub destination_start: T,
+}
+
+impl<T: Int> Segment<T>
+{
+    /// Only called with values of `source`, whose images were checked to fit
+    /// in `T` when the almanac lines were parsed.
+    fn apply(&self, value: T) -> T
+    {
+        let offset = value.checked_sub(self.source.start).unwrap();
+        self.destination_start.checked_add(offset).unwrap()
+    }
+
+    pub fn destination(&self) -> Range<T>
+    {
+        let len = self.source.end.checked_sub(self.source.start).unwrap();
+        Range {
+            start: self.destination_start,
+            end: self.destination_start.checked_add(len).unwrap(),
+        }
+    }
+}
+
+impl<T: Int> fmt::Display for Segment<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "{} -> {}", self.source, self.destination())
+    }
+}
+
+/// A composition of maps as sorted, disjoint segments; every value outside
+/// of them maps to itself.
+#[derive(Clone, Debug)]
+pub struct Piecewise<T>
+{
+    segments: Vec<Segment<T>>,
+}
+
+impl<T: Int> Piecewise<T>
+{
+    fn new(mut segments: Vec<Segment<T>>) -> Piecewise<T>
+    {
+        segments.retain(|s| s.destination_start != s.source.start);
+        segments.sort_by_key(|s| s.source.start);
+
+        let mut res = Vec::<Segment<T>>::new();
+        for segment in segments
+        {
+            if let Some(last) = res.last_mut()
+            {
+                if last.source.end == segment.source.start && last.destination().end == segment.destination_start
+                {
+                    last.source.end = segment.source.end;
+                    continue;
+                }
+            }
+            res.push(segment);
+        }
+
+        Piecewise { segments: res }
+    }
+
+    /// Later lines only apply to what earlier lines do not cover.
+    pub(super) fn from_map(map: &Map<T>) -> Piecewise<T>
+    {
+        let mut segments = Vec::<Segment<T>>::new();
+        let mut covered = Vec::<Range<T>>::new();
+
+        for line in &map.ranges
+        {
+            let source = match line.source() {
+                Some(v) => v,
+                None => continue,
+            };
+
+            let mut parts = vec![source];
+            for c in &covered
+            {
+                parts = parts.iter().flat_map(|p| p.subtract(c)).collect();
+            }
+
+            for part in parts
+            {
+                segments.push(Segment {
+                    source: part,
+                    destination_start: line.find_value(part.start).unwrap(),
+                });
+            }
+            covered.push(source);
+        }
+
+        Piecewise::new(segments)
+    }
+
+    pub fn segments(&self) -> &[Segment<T>]
+    {
+        &self.segments
+    }
+
+    pub fn get(&self, value: T) -> T
+    {
+        let i = self.segments.partition_point(|s| s.source.start <= value);
+
+        match i.checked_sub(1).map(|i| &self.segments[i]) {
+            Some(segment) if segment.source.contains(value) => segment.apply(value),
+            _ => value,
+        }
+    }
+
+    /// Cuts `range` along the segments, pairing each piece with the image
+    /// of its start.
+    fn split(&self, range: Range<T>) -> Vec<(Range<T>, T)>
+    {
+        let mut res = Vec::<(Range<T>, T)>::new();
+        let mut pending = vec![range];
+
+        for segment in &self.segments
+        {
+            let mut unmatched = Vec::<Range<T>>::new();
+            for r in pending
+            {
+                if let Some(inside) = r.intersect(&segment.source)
+                {
+                    res.push((inside, segment.apply(inside.start)));
+                }
+                unmatched.extend(r.subtract(&segment.source));
+            }
+            pending = unmatched;
+        }
+
+        res.extend(pending.into_iter().map(|r| (r, r.start)));
+        res
+    }
+
+    /// The function applying `self`, then `next`.
+    pub fn then(&self, next: &Piecewise<T>) -> Piecewise<T>
+    {
+        let mut segments = Vec::<Segment<T>>::new();
+
+        // Values `self` moves, wherever `next` sends them.
+        for segment in &self.segments
+        {
+            let image = segment.destination();
+            for (part, destination_start) in next.split(image)
+            {
+                let offset = part.start.checked_sub(image.start).unwrap();
+                let len = part.end.checked_sub(part.start).unwrap();
+                let start = segment.source.start.checked_add(offset).unwrap();
+                segments.push(Segment {
+                    source: Range { start, end: start.checked_add(len).unwrap() },
+                    destination_start,
+                });
+            }
+        }
+
+        // Values `self` leaves alone but `next` moves.
+        for segment in &next.segments
+        {
+            let mut gaps = vec![segment.source];
+            for s in &self.segments
+            {
+                gaps = gaps.iter().flat_map(|g| g.subtract(&s.source)).collect();
+            }
+
+            for gap in gaps
+            {
+                segments.push(Segment {
+                    source: gap,
+                    destination_start: segment.apply(gap.start),
+                });
+            }
+        }
+
+        Piecewise::new(segments)
+    }
+}
+
+impl<T: Int> Input<T>
+{
+    /// Composes every map from `from` to `to` into a single function.
+    pub fn piecewise(&self, from: &str, to: &str) -> Result<Piecewise<T>, Error>
+    {
+        Ok(self.chain(from, to)?
+            .iter()
+            .fold(Piecewise::new(Vec::new()), |f, map| f.then(&Piecewise::from_map(map))))
+    }
+}
+
+/// `table [from] [to]`: every segment of the composed function, with the
+/// identity gaps between them.
+pub fn table(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let input = Day5::parse(&source.load()?)?;
+    let from = args.first().map(|s| s.as_str()).unwrap_or("seed");
+    let to = args.get(1).map(|s| s.as_str()).unwrap_or("location");
+
+    let function = input.piecewise(from, to)?;
+    let segments = function.segments();
+
+    let mut res = vec![format!("{from} -> {to}")];
+    match (segments.first(), segments.last()) {
+        (Some(first), Some(last)) => {
+            if first.source.start > 0
+            {
+                res.push(format!("..{} -> identity", first.source.start));
+            }
+            for (i, segment) in segments.iter().enumerate()
+            {
+                res.push(segment.to_string());
+
+                let gap = segments.get(i + 1)
+                    .and_then(|next| Range::new(segment.source.end, next.source.start));
+                if let Some(gap) = gap
+                {
+                    res.push(format!("{gap} -> identity"));
+                }
+            }
+            res.push(format!("{}.. -> identity", last.source.end));
+        },
+        _ => res.push(".. -> identity".to_string()),
+    }
+
+    Ok(res.join("\n"))
+}
@@ -0,0 +1,309 @@
+use super::*;
+use crate::testing::Lcg;
+
+const EXAMPLE: &str = include_str!("../tests/examples/5.txt");
+
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+/// An almanac whose only non-empty map is `seed-to-soil`.
+fn almanac(seeds: &str, lines: &[&str]) -> String
+{
+    let mut res = format!("seeds: {seeds}\n");
+    for (i, map) in MAPS.iter().enumerate()
+    {
+        res += &format!("\n{map} map:\n");
+        if i == 0
+        {
+            for line in lines
+            {
+                res += &format!("{line}\n");
+            }
+        }
+    }
+
+    res
+}
+
+#[test]
+fn example_is_width_independent()
+{
+    let small = Input::<u32>::parse(EXAMPLE).unwrap();
+    let signed = Input::<i64>::parse(EXAMPLE).unwrap();
+
+    assert_eq!(part1::solve(&small).unwrap(), 35);
+    assert_eq!(part1::solve(&signed).unwrap(), 35);
+    assert_eq!(part2::solve(&small).unwrap(), 46);
+    assert_eq!(part2::solve(&signed).unwrap(), 46);
+}
+
+#[test]
+fn overflowing_line_is_reported()
+{
+    let text = almanac("5 1", &["4294967290 0 10"]);
+
+    let error = Input::<u32>::parse(&text).unwrap_err();
+    assert_eq!(error.line, 4);
+    assert_eq!(error.source_line, "4294967290 0 10");
+
+    let input = Input::<u64>::parse(&text).unwrap();
+    assert_eq!(part1::solve(&input).unwrap(), 4294967291);
+    assert_eq!(part2::solve(&input).unwrap(), 4294967295);
+}
+
+#[test]
+fn seed_range_overflow_is_reported()
+{
+    let text = almanac("4294967290 6", &[]);
+    let input = Input::<u32>::parse(&text).unwrap();
+
+    assert!(matches!(part2::solve(&input), Err(Error::Solve(_))));
+}
+
+#[test]
+fn almanac_without_seeds_has_no_answer()
+{
+    let input = Input::<u64>::parse(&almanac("", &["0 5 1"])).unwrap();
+
+    assert!(matches!(part1::solve(&input), Err(Error::Solve(_))));
+    assert!(matches!(part2::solve(&input), Err(Error::Solve(_))));
+    assert!(matches!(part2::solve_by_inverse(&input), Err(Error::Solve(_))));
+    assert!(matches!(part2::solve_brute_force(&input), Err(Error::Solve(_))));
+}
+
+#[test]
+fn negative_length_is_rejected()
+{
+    let text = almanac("5 1", &["0 0 -1"]);
+    let error = Input::<i64>::parse(&text).unwrap_err();
+
+    assert_eq!(error.expected, "a non-negative length");
+}
+
+#[test]
+fn converts_between_any_categories()
+{
+    let input = Input::<u64>::parse(EXAMPLE).unwrap();
+
+    assert_eq!(input.convert("seed", "soil", 79).unwrap(), 81);
+    assert_eq!(input.convert("seed", "water", 79).unwrap(), 81);
+    assert_eq!(input.convert("soil", "location", 81).unwrap(), 82);
+    assert_eq!(input.convert("light", "light", 3).unwrap(), 3);
+}
+
+#[test]
+fn custom_categories()
+{
+    let text = "seeds: 1 2\n\nseed-to-bean map:\n10 0 5\n\nbean-to-location map:\n0 10 3\n";
+    let input = Input::<u64>::parse(text).unwrap();
+
+    assert_eq!(input.convert("seed", "bean", 1).unwrap(), 11);
+    assert_eq!(part1::solve(&input).unwrap(), 1);
+}
+
+#[test]
+fn broken_and_ambiguous_chains()
+{
+    let text = "seeds: 1\n\na-to-b map:\n\nb-to-d map:\n\na-to-c map:\n\nc-to-d map:\n";
+    let input = Input::<u64>::parse(text).unwrap();
+
+    assert!(matches!(input.convert("b", "c", 1), Err(Error::Solve(_))));
+    match input.convert("a", "d", 1) {
+        Err(Error::Solve(e)) => assert!(e.contains("a -> b -> d") && e.contains("a -> c -> d"), "{e}"),
+        res => panic!("expected an ambiguous chain, got {res:?}"),
+    }
+    assert!(matches!(part1::solve(&input), Err(Error::Solve(_))));
+}
+
+#[test]
+fn malformed_header()
+{
+    let text = "seeds: 1\n\nseed-soil map:\n1 2 3\n";
+    let error = Input::<u64>::parse(text).unwrap_err();
+
+    assert_eq!(error.line, 3);
+    assert_eq!(error.found, "`seed-soil`");
+}
+
+#[test]
+fn inverse_round_trips()
+{
+    let input = Input::<u64>::parse(EXAMPLE).unwrap();
+
+    input.check_invertible("seed", "location").unwrap();
+    input.check_round_trip("seed", "location", &(0..100).collect::<Vec<u64>>()).unwrap();
+    assert_eq!(input.inverse("seed", "location", 46).unwrap(), Some(82));
+    assert_eq!(input.invert("soil", "fertilizer", 39).unwrap(), vec![0]);
+}
+
+#[test]
+fn search_from_the_lowest_location()
+{
+    let input = Input::<u64>::parse(EXAMPLE).unwrap();
+
+    assert_eq!(part2::solve_by_inverse(&input).unwrap(), 46);
+}
+
+#[test]
+fn non_injective_maps_are_reported()
+{
+    // 5 and 10 both become 10.
+    let text = almanac("5 1", &["10 5 1"]);
+    let input = Input::<u64>::parse(&text).unwrap();
+
+    assert_eq!(input.invert("seed", "soil", 10).unwrap(), vec![5, 10]);
+    assert!(matches!(input.inverse("seed", "soil", 10), Err(Error::Solve(_))));
+    assert!(matches!(input.check_invertible("seed", "soil"), Err(Error::Solve(_))));
+
+    let overlapping = almanac("5 1", &["20 0 10", "30 5 10"]);
+    let input = Input::<u64>::parse(&overlapping).unwrap();
+    match input.check_invertible("seed", "location") {
+        Err(Error::Solve(e)) => assert!(e.contains("overlap on sources 5..10"), "{e}"),
+        res => panic!("expected overlapping sources, got {res:?}"),
+    }
+}
+
+#[test]
+fn piecewise_matches_the_chain()
+{
+    let input = Input::<u64>::parse(EXAMPLE).unwrap();
+    let function = input.piecewise("seed", "location").unwrap();
+
+    for seed in 0..120
+    {
+        assert_eq!(function.get(seed), input.convert("seed", "location", seed).unwrap(), "seed {seed}");
+    }
+
+    let segments = function.segments();
+    assert!(segments.windows(2).all(|w| w[0].source.end <= w[1].source.start));
+    assert_eq!(segments[0].to_string(), "0..14 -> 22..36");
+}
+
+#[test]
+fn piecewise_respects_first_matching_line()
+{
+    let text = almanac("5 1", &["20 0 10", "30 5 10"]);
+    let input = Input::<u64>::parse(&text).unwrap();
+    let function = input.piecewise("seed", "soil").unwrap();
+
+    assert_eq!(function.get(7), 27);
+    assert_eq!(function.get(12), 37);
+    assert_eq!(function.get(15), 15);
+}
+
+#[test]
+fn explain_names_each_matching_line()
+{
+    let input = Input::<u64>::parse(EXAMPLE).unwrap();
+
+    let res = trace::explain_seed(&input, 79).unwrap();
+    assert!(res.contains("seed 79 -> soil 81 (line `52 50 48`)"), "{res}");
+    assert!(res.contains("soil 81 -> fertilizer 81 (identity, no range matched)"), "{res}");
+    assert!(res.ends_with("location 82"), "{res}");
+
+    let res = trace::explain_range(&input, 82).unwrap();
+    assert!(res.contains("77..88 -> 45..56 (line `45 77 23`)"), "{res}");
+    assert!(res.ends_with("lowest location 46"), "{res}");
+}
+
+#[test]
+fn lint_accepts_example()
+{
+    let input = Input::<u64>::parse(EXAMPLE).unwrap();
+    let reports = lint::lint(&input);
+
+    assert_eq!(reports.len(), 7);
+    assert!(reports.iter().all(|r| r.issues.is_empty()));
+    assert_eq!(reports[0].to_string(), "seed-to-soil: 2 lines, sources 50..100, 50 values covered");
+}
+
+#[test]
+fn lint_reports_overlaps_gaps_and_empty_lines()
+{
+    let text = almanac("1", &["20 0 10", "25 5 10", "100 30 5", "7 7 0"]);
+    let input = Input::<u64>::parse(&text).unwrap();
+    let report = &lint::lint(&input)[0];
+
+    assert_eq!(report.span, Some(Range { start: 0, end: 35 }));
+    assert_eq!(report.covered, 20);
+    assert_eq!(report.issues, vec![
+        lint::Issue::ZeroLength("7 7 0".to_string()),
+        lint::Issue::OverlappingSources("20 0 10".to_string(), "25 5 10".to_string(), Range { start: 5, end: 10 }),
+        lint::Issue::OverlappingDestinations("20 0 10".to_string(), "25 5 10".to_string(), Range { start: 25, end: 30 }),
+        lint::Issue::Gap(Range { start: 15, end: 30 }),
+    ]);
+}
+
+#[test]
+fn graph_exports_chain_and_ranges()
+{
+    let input = Input::<u64>::parse(EXAMPLE).unwrap();
+
+    let res = graph::dot(&input);
+    assert!(res.contains("\"seed\" -> \"soil\" [label=\"98..100 -> 50..52\\l50..98 -> 52..100\\l\"];"), "{res}");
+
+    let res = graph::svg(&input, "seed", "soil").unwrap();
+    assert_eq!(res.matches("<rect").count(), 2);
+    assert!(res.contains("<title>50..98 -&gt; 52..100</title>"), "{res}");
+    assert!(res.contains("<title>0..50 -&gt; identity</title>"), "{res}");
+}
+
+/// Small almanacs with overlapping, shuffled lines, from a fixed seed.
+fn random_almanacs(count: usize) -> Vec<String>
+{
+    let mut rng = Lcg::new(0x2545_f491);
+    let mut next = |max: u64| rng.next(max);
+
+    (0..count)
+        .map(|_| {
+            let seeds = (0..4)
+                .map(|_| format!("{} {}", next(100), next(30)))
+                .collect::<Vec<String>>();
+            let mut res = format!("seeds: {}\n", seeds.join(" "));
+            for map in MAPS
+            {
+                res += &format!("\n{map} map:\n");
+                for _ in 0..1 + next(4)
+                {
+                    res += &format!("{} {} {}\n", next(120), next(120), 1 + next(40));
+                }
+            }
+
+            res
+        })
+        .collect()
+}
+
+#[test]
+fn strategies_match_brute_force()
+{
+    let input = Input::<u64>::parse(EXAMPLE).unwrap();
+    assert_eq!(part2::solve_brute_force(&input).unwrap(), 46);
+
+    for text in random_almanacs(20)
+    {
+        let input = Input::<u64>::parse(&text).unwrap();
+        let expected = part2::solve_brute_force(&input).unwrap();
+
+        assert_eq!(part2::solve(&input).unwrap(), expected, "{text}");
+        assert_eq!(part2::solve_by_inverse(&input).unwrap(), expected, "{text}");
+    }
+}
+
+#[test]
+fn brute_force_spans_several_chunks()
+{
+    let text = almanac("-100000 200000", &["-5 -99990 10"]);
+    let input = Input::<i32>::parse(&text).unwrap();
+
+    assert_eq!(part2::solve_brute_force(&input).unwrap(), -100000);
+    assert_eq!(part2::solve(&input).unwrap(), -100000);
+    assert_eq!(part2::solve_by_inverse(&input).unwrap(), -100000);
+}
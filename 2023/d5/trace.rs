@@ -0,0 +1,80 @@
+use common::command::arg;
+use common::input::Source;
+use common::{Error, Part, Solution};
+
+use super::part2::{seed_ranges, split_ranges};
+use super::{input_range_find_line, Day5, Input, InputLine, Int, Range};
+
+fn describe_line<T: Int>(line: Option<&InputLine<T>>) -> String
+{
+    match line {
+        Some(line) => format!("line `{line}`"),
+        None => "identity, no range matched".to_string(),
+    }
+}
+
+/// Every hop of a single seed from `seed` to `location`.
+pub fn explain_seed<T: Int>(input: &Input<T>, seed: T) -> Result<String, Error>
+{
+    let mut res = vec![format!("seed {seed}")];
+    let mut value = seed;
+
+    for map in input.chain("seed", "location")?
+    {
+        let line = input_range_find_line(&map.ranges, value);
+        let next = map.get_value(value);
+        res.push(format!("  {} {value} -> {} {next} ({})", map.source, map.destination, describe_line(line)));
+        value = next;
+    }
+
+    res.push(format!("location {value}"));
+    Ok(res.join("\n"))
+}
+
+/// How the part 2 seed range holding `seed` is split by every map, down to
+/// its lowest location.
+pub fn explain_range<T: Int>(input: &Input<T>, seed: T) -> Result<String, Error>
+{
+    let range = match seed_ranges(input)?.into_iter().find(|r| r.contains(seed)) {
+        Some(v) => v,
+        None => return Err(Error::Solve(format!("seed {seed} is in none of the seed ranges"))),
+    };
+
+    let mut res = vec![format!("seed range {range}")];
+    let mut ranges = vec![range];
+
+    for map in input.chain("seed", "location")?
+    {
+        res.push(format!("  {}-to-{}:", map.source, map.destination));
+
+        let splits = split_ranges(&map.ranges, ranges);
+        for split in &splits
+        {
+            res.push(format!("    {} -> {} ({})", split.from, split.to, describe_line(split.line)));
+        }
+
+        ranges = splits.iter().map(|s| s.to).collect::<Vec<Range<T>>>();
+    }
+
+    let lowest = ranges.iter().map(|r| r.start).min();
+    if let Some(lowest) = lowest
+    {
+        res.push(format!("lowest location {lowest}"));
+    }
+
+    Ok(res.join("\n"))
+}
+
+/// `explain <part> <seed>`: traces a seed, or for part 2 the seed range
+/// containing it.
+pub fn explain(source: &Source, args: &[String]) -> Result<String, Error>
+{
+    let part: Part = arg(args, 0, "part")?;
+    let seed: u64 = arg(args, 1, "seed")?;
+    let input = Day5::parse(&source.load()?)?;
+
+    match part {
+        Part::One => explain_seed(&input, seed),
+        Part::Two => explain_range(&input, seed),
+    }
+}
@@ -0,0 +1,33 @@
+use common::{solution, Command, Runner};
+
+pub mod d1;
+pub mod d2;
+pub mod d3;
+pub mod d4;
+pub mod d5;
+
+#[cfg(test)]
+mod testing;
+
+pub fn get_runner(day: u32) -> Option<Runner>
+{
+    match day {
+        1 => Some(solution::run::<d1::Day1>),
+        2 => Some(solution::run::<d2::Day2>),
+        3 => Some(solution::run::<d3::Day3>),
+        4 => Some(solution::run::<d4::Day4>),
+        5 => Some(solution::run::<d5::Day5>),
+        _ => None,
+    }
+}
+
+pub fn get_commands(day: u32) -> &'static [Command]
+{
+    match day {
+        2 => d2::COMMANDS,
+        3 => d3::COMMANDS,
+        4 => d4::COMMANDS,
+        5 => d5::COMMANDS,
+        _ => &[],
+    }
+}
@@ -0,0 +1,24 @@
+/// A seeded linear congruential generator, so random tests see the same
+/// values on every run.
+pub struct Lcg
+{
+    state: u64,
+}
+
+impl Lcg
+{
+    pub fn new(seed: u64) -> Lcg
+    {
+        Lcg { state: seed }
+    }
+
+    /// A value in `0..max`.
+    pub fn next(&mut self, max: u64) -> u64
+    {
+        self.state = self.state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+
+        (self.state >> 33) % max
+    }
+}
@@ -0,0 +1,40 @@
+use std::path::{Path, PathBuf};
+
+use common::golden;
+use common::input::Source;
+
+const YEAR: u32 = 2023;
+
+fn check(dir: &Path, answers: &[golden::Expected])
+{
+    let failures = golden::check(dir, answers, aoc2023::get_runner);
+    assert!(failures.is_empty(), "\n{}", failures.join("\n"));
+}
+
+#[test]
+fn examples()
+{
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/examples");
+    let answers = golden::read_answers(&dir.join("answers")).unwrap();
+
+    check(&dir, &answers);
+}
+
+/// Private inputs live in the gitignored `inputs/<year>/` directory next to an
+/// `answers` file; the test is a no-op when it is absent.
+#[test]
+fn private_inputs()
+{
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+    let dir = root.join(Source::default_dir(YEAR));
+    let path = dir.join("answers");
+    if !path.exists()
+    {
+        eprintln!("skipping private inputs: {} not found", path.display());
+        return;
+    }
+
+    let answers = golden::read_answers(&path).unwrap();
+
+    check(&dir, &answers);
+}
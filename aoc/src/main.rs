@@ -0,0 +1,174 @@
+use std::env;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use common::input::Source;
+use common::{Command, Part, Runner};
+
+const USAGE: &str = "\
+Usage: aoc run <year> <day> <part> [input|-] [--explain[=<arg>]]
+       aoc commands <year> <day>
+       aoc <command> <year> <day> [args...] [--input <input|->]
+
+--explain runs the day's `explain` command, passing it the part and `arg`:
+  2023 day 2: an optional bag or @file, the puzzle's bag by default
+  2023 day 5: the seed to follow";
+
+fn get_runner(year: u32, day: u32) -> Option<Runner>
+{
+    match year {
+        2023 => aoc2023::get_runner(day),
+        _ => None,
+    }
+}
+
+fn get_commands(year: u32, day: u32) -> &'static [Command]
+{
+    match year {
+        2023 => aoc2023::get_commands(day),
+        _ => &[],
+    }
+}
+
+fn format_duration(duration: Duration) -> String
+{
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn parse_date(args: &[String]) -> Result<(u32, u32), String>
+{
+    if args.len() < 2
+    {
+        return Err(USAGE.to_string());
+    }
+
+    let year: u32 = args[0].parse().map_err(|_| format!("invalid year `{}`", args[0]))?;
+    let day: u32 = args[1].parse().map_err(|_| format!("invalid day `{}`", args[1]))?;
+
+    Ok((year, day))
+}
+
+/// Removes `--explain[=<arg>]` from `args`, returning its value, if any, as
+/// the arguments to pass on. The value is only ever joined by `=`, so the
+/// next argument stays the input.
+fn take_explain(args: &[String]) -> (Vec<String>, Option<Vec<String>>)
+{
+    let mut rest = Vec::<String>::new();
+    let mut explain = None;
+
+    for arg in args
+    {
+        match arg.strip_prefix("--explain") {
+            Some("") => explain = Some(Vec::new()),
+            Some(v) if v.starts_with('=') => explain = Some(vec![v[1..].to_string()]),
+            _ => rest.push(arg.clone()),
+        }
+    }
+
+    (rest, explain)
+}
+
+fn run(args: &[String]) -> Result<String, String>
+{
+    let (args, explain) = take_explain(args);
+    if args.len() < 3 || args.len() > 4
+    {
+        return Err(USAGE.to_string());
+    }
+
+    let (year, day) = parse_date(&args)?;
+    let part: Part = args[2].parse()?;
+
+    let source = Source::resolve(args.get(3).map(|s| s.as_str()), year, day);
+
+    if let Some(explain) = explain
+    {
+        let command = match get_commands(year, day).iter().find(|c| c.name == "explain") {
+            Some(v) => v,
+            None => return Err(format!("no explain mode for {year} day {day}")),
+        };
+
+        let args = [vec![part.to_string()], explain].concat();
+        return (command.run)(&source, &args)
+            .map_err(|e| e.with_file(&source.name()).to_string());
+    }
+
+    let runner = match get_runner(year, day) {
+        Some(v) => v,
+        None => return Err(format!("no solution for {year} day {day}")),
+    };
+
+    let input = source.load().map_err(|e| e.to_string())?;
+
+    let report = runner(&input, part).map_err(|e| e.with_file(&source.name()).to_string())?;
+    eprintln!("parse: {}, solve: {}",
+        format_duration(report.parse_time),
+        format_duration(report.solve_time));
+
+    Ok(report.answer)
+}
+
+fn list_commands(args: &[String]) -> Result<String, String>
+{
+    let (year, day) = parse_date(args)?;
+
+    let res = get_commands(year, day)
+        .iter()
+        .map(|c| format!("aoc {} {year} {day} {}", c.name, c.usage))
+        .collect::<Vec<String>>();
+
+    Ok(res.join("\n"))
+}
+
+fn run_command(name: &str, args: &[String]) -> Result<String, String>
+{
+    let (year, day) = parse_date(args)?;
+
+    let command = match get_commands(year, day).iter().find(|c| c.name == name) {
+        Some(v) => v,
+        None => return Err(format!("unknown command `{name}` for {year} day {day}\n{USAGE}")),
+    };
+
+    let mut input = None;
+    let mut rest = Vec::<String>::new();
+    let mut iter = args[2..].iter();
+    while let Some(arg) = iter.next()
+    {
+        match arg.as_str() {
+            "--input" | "-i" => match iter.next() {
+                Some(v) => input = Some(v.as_str()),
+                None => return Err(format!("missing value for {arg}")),
+            },
+            _ => rest.push(arg.clone()),
+        }
+    }
+
+    let source = Source::resolve(input, year, day);
+    (command.run)(&source, &rest).map_err(|e| match e {
+        common::Error::Usage(e) => format!("{e}\nUsage: aoc {name} {year} {day} {}", command.usage),
+        e => e.with_file(&source.name()).to_string(),
+    })
+}
+
+fn main() -> ExitCode
+{
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    let res = match args.first().map(|s| s.as_str()) {
+        Some("run") => run(&args[1..]),
+        Some("commands") => list_commands(&args[1..]),
+        Some(name) => run_command(name, &args[1..]),
+        None => Err(USAGE.to_string()),
+    };
+
+    match res {
+        Ok(answer) => {
+            println!("{answer}");
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        },
+    }
+}
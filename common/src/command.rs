@@ -0,0 +1,24 @@
+use std::str::FromStr;
+
+use crate::input::Source;
+use crate::Error;
+
+/// A day-specific subcommand, run as `aoc <name> <year> <day> [args...]`.
+pub struct Command
+{
+    pub name: &'static str,
+    pub usage: &'static str,
+    pub run: fn(&Source, &[String]) -> Result<String, Error>,
+}
+
+/// Parses the positional argument `index`, described as `name` in errors.
+pub fn arg<T: FromStr>(args: &[String], index: usize, name: &str) -> Result<T, Error>
+{
+    let value = match args.get(index) {
+        Some(v) => v,
+        None => return Err(Error::Usage(format!("missing {name}"))),
+    };
+
+    value.parse()
+        .map_err(|_| Error::Usage(format!("invalid {name} `{value}`")))
+}
@@ -0,0 +1,166 @@
+use std::fmt;
+use std::io;
+use std::ops::Range;
+
+use crate::input;
+
+#[derive(Debug)]
+pub enum Error
+{
+    Io(io::Error),
+    Parse(ParseError),
+    /// The input parsed but cannot be solved as given.
+    Solve(String),
+    Usage(String),
+}
+
+impl Error
+{
+    /// Attaches the input file name to parse errors for rendering.
+    pub fn with_file(self, file: &str) -> Error
+    {
+        match self {
+            Error::Parse(e) => Error::Parse(e.with_file(file)),
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for Error
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self {
+            Error::Io(e) => write!(f, "{e}"),
+            Error::Parse(e) => write!(f, "{e}"),
+            Error::Solve(e) => write!(f, "{e}"),
+            Error::Usage(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error
+{
+    fn from(e: io::Error) -> Self
+    {
+        Error::Io(e)
+    }
+}
+
+impl From<ParseError> for Error
+{
+    fn from(e: ParseError) -> Self
+    {
+        Error::Parse(e)
+    }
+}
+
+/// A parse failure pointing at a span of a single input line.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError
+{
+    pub file: Option<String>,
+    /// 1-based, 0 until the caller knows which line it was.
+    pub line: usize,
+    /// Byte range within `source_line`.
+    pub columns: Range<usize>,
+    pub expected: String,
+    pub found: String,
+    pub source_line: String,
+}
+
+impl ParseError
+{
+    /// `found` must be a slice of `line`; an empty slice points between two
+    /// characters, typically at the end of the line.
+    pub fn new(line: &str, found: &str, expected: impl Into<String>) -> ParseError
+    {
+        let start = input::offset(line, found).unwrap_or(line.len());
+        let end = start + found.len();
+
+        let found = match (found.is_empty(), start >= line.len()) {
+            (true, true) => "end of line".to_string(),
+            (true, false) => "nothing".to_string(),
+            (false, _) => format!("`{found}`"),
+        };
+
+        ParseError {
+            file: None,
+            line: 0,
+            columns: start..end,
+            expected: expected.into(),
+            found,
+            source_line: line.to_string(),
+        }
+    }
+
+    pub fn with_line(mut self, line: usize) -> ParseError
+    {
+        self.line = line;
+        self
+    }
+
+    /// Keeps the file already attached, if any, since errors in a secondary
+    /// file pass through the handler of the main input.
+    pub fn with_file(mut self, file: &str) -> ParseError
+    {
+        self.file.get_or_insert_with(|| file.to_string());
+        self
+    }
+}
+
+impl fmt::Display for ParseError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let prefix = &self.source_line[..self.columns.start];
+        let column = prefix.chars().count();
+        let width = self.source_line[self.columns.clone()].chars().count().max(1);
+
+        let file = self.file.as_deref().unwrap_or("<input>");
+        let gutter = " ".repeat(self.line.to_string().len());
+
+        writeln!(f, "expected {}, found {}", self.expected, self.found)?;
+        writeln!(f, "{gutter}--> {file}:{}:{}", self.line, column + 1)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.source_line)?;
+        write!(f, "{gutter} | {}{}", " ".repeat(column), "^".repeat(width))
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn renders_caret_under_span()
+    {
+        let line = "Game 2: 3 blue, 4 purple";
+        let error = ParseError::new(line, &line[18..], "a colour")
+            .with_line(12)
+            .with_file("2.txt");
+
+        assert_eq!(error.columns, 18..24);
+        assert_eq!(error.to_string(), "\
+expected a colour, found `purple`
+  --> 2.txt:12:19
+   |
+12 | Game 2: 3 blue, 4 purple
+   |                   ^^^^^^");
+    }
+
+    #[test]
+    fn empty_span_at_end_of_line()
+    {
+        let line = "Card 1";
+        let error = ParseError::new(line, &line[line.len()..], "`:`");
+
+        assert_eq!(error.found, "end of line");
+        assert_eq!(error.columns, 6..6);
+    }
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::input::Source;
+use crate::{Part, Runner};
+
+/// One line of an answers file: `<day> <part> <answer> [input file]`.
+#[derive(Debug)]
+pub struct Expected
+{
+    pub day: u32,
+    pub part: Part,
+    pub answer: String,
+    pub file: Option<String>,
+}
+
+impl Expected
+{
+    fn parse(line: &str) -> Result<Expected, String>
+    {
+        let fields = line.split_whitespace().collect::<Vec<&str>>();
+        if fields.len() < 3 || fields.len() > 4
+        {
+            return Err(format!("expected `<day> <part> <answer> [file]`, found `{line}`"));
+        }
+
+        Ok(Expected {
+            day: fields[0].parse().map_err(|_| format!("invalid day `{}`", fields[0]))?,
+            part: fields[1].parse()?,
+            answer: fields[2].to_string(),
+            file: fields.get(3).map(|s| s.to_string()),
+        })
+    }
+
+    /// An explicit file wins, then `<day>-<part>.txt`, then `<day>.txt`.
+    fn input_path(&self, dir: &Path) -> std::path::PathBuf
+    {
+        if let Some(file) = &self.file
+        {
+            return dir.join(file);
+        }
+
+        let per_part = dir.join(format!("{}-{}.txt", self.day, self.part));
+        if per_part.exists()
+        {
+            return per_part;
+        }
+
+        dir.join(format!("{}.txt", self.day))
+    }
+}
+
+/// Reads an answers file, ignoring blank lines and `#` comments.
+pub fn read_answers(path: &Path) -> io::Result<Vec<Expected>>
+{
+    fs::read_to_string(path)?
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        .map(|l| Expected::parse(l)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()))))
+        .collect()
+}
+
+/// Runs every expected answer against its input in `dir` and returns one
+/// message per mismatch.
+pub fn check(dir: &Path, answers: &[Expected], get_runner: fn(u32) -> Option<Runner>) -> Vec<String>
+{
+    let mut failures = Vec::<String>::new();
+
+    for expected in answers
+    {
+        let path = expected.input_path(dir);
+        let name = format!("day {} part {} ({})", expected.day, expected.part, path.display());
+
+        let runner = match get_runner(expected.day) {
+            Some(v) => v,
+            None => {
+                failures.push(format!("{name}: no solution"));
+                continue;
+            },
+        };
+
+        let input = match Source::Path(path.clone()).load() {
+            Ok(v) => v,
+            Err(e) => {
+                failures.push(format!("{name}: {e}"));
+                continue;
+            },
+        };
+
+        match runner(&input, expected.part) {
+            Ok(report) if report.answer == expected.answer => (),
+            Ok(report) => failures.push(format!("{name}: expected {}, found {}", expected.answer, report.answer)),
+            Err(e) => failures.push(format!("{name}: {e}")),
+        }
+    }
+
+    failures
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+use crate::input;
+
+/// Cell coordinates, `x` being the column and `y` the row.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point
+{
+    pub x: usize,
+    pub y: usize,
+}
+
+/// A signed move from one cell to another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Offset
+{
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Offset
+{
+    pub const fn new(dx: isize, dy: isize) -> Offset
+    {
+        Offset { dx, dy }
+    }
+}
+
+/// Up, right, down and left.
+pub const NEIGHBOURS4: [Offset; 4] = [
+    Offset::new(0, -1),
+    Offset::new(1, 0),
+    Offset::new(0, 1),
+    Offset::new(-1, 0),
+];
+
+/// The four sides and the four corners, row by row.
+pub const NEIGHBOURS8: [Offset; 8] = [
+    Offset::new(-1, -1),
+    Offset::new(0, -1),
+    Offset::new(1, -1),
+    Offset::new(-1, 0),
+    Offset::new(1, 0),
+    Offset::new(-1, 1),
+    Offset::new(0, 1),
+    Offset::new(1, 1),
+];
+
+impl Point
+{
+    pub fn new(x: usize, y: usize) -> Point
+    {
+        Point { x, y }
+    }
+
+    /// `None` when the move would go left of or above the origin.
+    pub fn offset(self, offset: Offset) -> Option<Point>
+    {
+        Some(Point {
+            x: self.x.checked_add_signed(offset.dx)?,
+            y: self.y.checked_add_signed(offset.dy)?,
+        })
+    }
+}
+
+impl fmt::Display for Point
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// A rectangular matrix of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T>
+{
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T>
+{
+    /// Reads one row per line, turning each char into a cell with `cell`.
+    /// Every line must be as long as the first one, which cannot be empty.
+    pub fn parse<F>(text: &str, mut cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> T,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::<T>::new();
+
+        for (y, line) in input::lines(text).enumerate()
+        {
+            let len = line.chars().count();
+            if width.is_none() && len == 0
+            {
+                return Err(ParseError::new(line, line, "a grid line").with_line(y + 1));
+            }
+
+            let width = *width.get_or_insert(len);
+            if len != width
+            {
+                let end = line.char_indices().nth(width).map_or(line.len(), |(i, _)| i);
+                let expected = format!("a line of {width} cells like the first one");
+                return Err(ParseError::new(line, &line[end..], expected).with_line(y + 1));
+            }
+
+            cells.extend(line.chars().map(&mut cell));
+            height += 1;
+        }
+
+        match width {
+            Some(width) => Ok(Grid { width, height, cells }),
+            None => Err(ParseError::new("", "", "a grid line").with_line(1)),
+        }
+    }
+
+    /// A grid of the same shape with `f` applied to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize
+    {
+        self.width
+    }
+
+    pub fn height(&self) -> usize
+    {
+        self.height
+    }
+
+    pub fn contains(&self, point: Point) -> bool
+    {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T>
+    {
+        match self.contains(point) {
+            true => self.cells.get(point.y * self.width + point.x),
+            false => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T>
+    {
+        match self.contains(point) {
+            true => self.cells.get_mut(point.y * self.width + point.x),
+            false => None,
+        }
+    }
+
+    /// The cells `offsets` away from `point` that lie within the grid.
+    pub fn neighbours<'a>(&self, point: Point, offsets: &'a [Offset]) -> impl Iterator<Item = Point> + 'a
+    {
+        let (width, height) = (self.width, self.height);
+        offsets
+            .iter()
+            .filter_map(move |&offset| point.offset(offset))
+            .filter(move |p| p.x < width && p.y < height)
+    }
+
+    pub fn neighbours4(&self, point: Point) -> impl Iterator<Item = Point>
+    {
+        self.neighbours(point, &NEIGHBOURS4)
+    }
+
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = Point>
+    {
+        self.neighbours(point, &NEIGHBOURS8)
+    }
+
+    /// Every point, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point>
+    {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new(i % width, i / width))
+    }
+
+    pub fn row(&self, y: usize) -> &[T]
+    {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]>
+    {
+        self.cells.chunks(self.width)
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T>
+    {
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>>
+    {
+        (0..self.width).map(|x| self.column(x))
+    }
+}
+
+impl<T> Index<Point> for Grid<T>
+{
+    type Output = T;
+
+    fn index(&self, point: Point) -> &T
+    {
+        match self.get(point) {
+            Some(v) => v,
+            None => panic!("{point} is outside of a {}x{} grid", self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T>
+{
+    fn index_mut(&mut self, point: Point) -> &mut T
+    {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(point) {
+            Some(v) => v,
+            None => panic!("{point} is outside of a {width}x{height} grid"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn parses_and_walks_a_grid()
+    {
+        let grid = Grid::parse("ab\ncd\nef", |c| c).unwrap();
+
+        assert_eq!((grid.width(), grid.height()), (2, 3));
+        assert_eq!(grid[Point::new(1, 2)], 'f');
+        assert_eq!(grid.get(Point::new(2, 0)), None);
+        assert_eq!(grid.row(1), &['c', 'd']);
+        assert_eq!(grid.column(1).collect::<String>(), "bdf");
+        assert_eq!(grid.columns().count(), 2);
+
+        let corner = grid.neighbours8(Point::new(0, 0)).collect::<Vec<Point>>();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1), Point::new(1, 1)]);
+        assert_eq!(grid.neighbours4(Point::new(1, 1)).count(), 3);
+    }
+
+    #[test]
+    fn rejects_ragged_lines()
+    {
+        let error = Grid::parse("abc\nab\n", |c| c).unwrap_err();
+
+        assert_eq!(error.line, 2);
+        assert_eq!(error.expected, "a line of 3 cells like the first one");
+    }
+
+    #[test]
+    fn rejects_an_empty_first_line()
+    {
+        assert_eq!(Grid::parse("\n\n", |c| c).unwrap_err().line, 1);
+        assert_eq!(Grid::parse("\nab\n", |c| c).unwrap_err().expected, "a grid line");
+    }
+}
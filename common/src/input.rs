@@ -0,0 +1,321 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::PathBuf;
+
+use crate::error::ParseError;
+
+/// Where a puzzle input is read from.
+#[derive(Clone, Debug)]
+pub enum Source
+{
+    Path(PathBuf),
+    Stdin,
+}
+
+impl Source
+{
+    /// `-` reads stdin, any other argument is a path, and no argument falls
+    /// back to `inputs/<year>/<day>.txt`.
+    pub fn resolve(arg: Option<&str>, year: u32, day: u32) -> Source
+    {
+        match arg {
+            Some("-") => Source::Stdin,
+            Some(path) => Source::Path(PathBuf::from(path)),
+            None => Source::Path(Source::default_path(year, day)),
+        }
+    }
+
+    pub fn default_dir(year: u32) -> PathBuf
+    {
+        PathBuf::from("inputs").join(year.to_string())
+    }
+
+    pub fn default_path(year: u32, day: u32) -> PathBuf
+    {
+        Source::default_dir(year).join(format!("{day}.txt"))
+    }
+
+    pub fn name(&self) -> String
+    {
+        match self {
+            Source::Path(path) => path.display().to_string(),
+            Source::Stdin => "<stdin>".to_string(),
+        }
+    }
+
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>>
+    {
+        match self {
+            Source::Path(path) => {
+                let file = fs::File::open(path)
+                    .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", path.display())))?;
+                Ok(Box::new(BufReader::new(file)))
+            },
+            Source::Stdin => Ok(Box::new(BufReader::new(io::stdin()))),
+        }
+    }
+
+    /// Reads the whole input and normalizes it.
+    pub fn load(&self) -> io::Result<String>
+    {
+        let mut raw = String::new();
+        self.open()?.read_to_string(&mut raw)?;
+
+        Ok(normalize(&raw))
+    }
+}
+
+/// Strips a leading BOM, turns CRLF into LF and drops trailing blank lines.
+pub fn normalize(raw: &str) -> String
+{
+    let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+
+    let mut lines = raw
+        .split('\n')
+        .map(|l| l.strip_suffix('\r').unwrap_or(l))
+        .collect::<Vec<&str>>();
+
+    while let Some(last) = lines.last()
+    {
+        if !last.trim().is_empty()
+        {
+            break;
+        }
+        lines.pop();
+    }
+
+    let mut res = lines.join("\n");
+    if !res.is_empty()
+    {
+        res.push('\n');
+    }
+
+    res
+}
+
+/// Byte offset of `inner` within `outer`, if it is a slice of it.
+pub fn offset(outer: &str, inner: &str) -> Option<usize>
+{
+    let start = outer.as_ptr() as usize;
+    let pos = inner.as_ptr() as usize;
+
+    if pos < start || pos + inner.len() > start + outer.len()
+    {
+        return None;
+    }
+
+    Some(pos - start)
+}
+
+/// 1-based number of the line where `inner`, a slice of `input`, starts.
+pub fn line_number(input: &str, inner: &str) -> usize
+{
+    let offset = offset(input, inner).unwrap_or(0);
+
+    input[..offset].matches('\n').count() + 1
+}
+
+/// Parses each line with `parse`, tagging errors with their line number.
+pub fn parse_lines<T, F>(input: &str, parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input.lines()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.with_line(i + 1)))
+        .collect()
+}
+
+/// The lines of `reader` as `normalize` would leave them, numbered from 1.
+/// Blank lines are only counted until a line follows them, so trailing ones
+/// never come out and the others come out empty.
+pub fn stream_lines<R: BufRead>(reader: R) -> StreamLines<R>
+{
+    StreamLines {
+        lines: reader.lines(),
+        read: 0,
+        number: 0,
+        blank: 0,
+        held: None,
+    }
+}
+
+pub struct StreamLines<R>
+{
+    lines: io::Lines<R>,
+    read: usize,
+    number: usize,
+    /// Blank lines read before `held`.
+    blank: usize,
+    held: Option<String>,
+}
+
+impl<R: BufRead> Iterator for StreamLines<R>
+{
+    type Item = io::Result<(usize, String)>;
+
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        while self.held.is_none()
+        {
+            let mut line = match self.lines.next()? {
+                Ok(v) => v,
+                Err(e) => return Some(Err(e)),
+            };
+            if line.ends_with('\r')
+            {
+                line.pop();
+            }
+            if self.read == 0 && line.starts_with('\u{feff}')
+            {
+                line.remove(0);
+            }
+            self.read += 1;
+
+            match line.trim().is_empty() {
+                true => self.blank += 1,
+                false => self.held = Some(line),
+            }
+        }
+
+        let line = match self.blank {
+            0 => self.held.take()?,
+            _ => {
+                self.blank -= 1;
+                String::new()
+            },
+        };
+
+        self.number += 1;
+        Some(Ok((self.number, line)))
+    }
+}
+
+pub fn lines(input: &str) -> std::str::Lines<'_>
+{
+    input.lines()
+}
+
+/// Iterates over the blank-line separated paragraphs of `input`.
+pub fn blocks(input: &str) -> Blocks<'_>
+{
+    Blocks { rest: input }
+}
+
+pub struct Blocks<'a>
+{
+    rest: &'a str,
+}
+
+fn split_line(s: &str) -> (&str, &str)
+{
+    match s.split_once('\n') {
+        Some((line, rest)) => (line, rest),
+        None => (s, ""),
+    }
+}
+
+impl<'a> Iterator for Blocks<'a>
+{
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str>
+    {
+        loop
+        {
+            if self.rest.is_empty()
+            {
+                return None;
+            }
+
+            let (line, rest) = split_line(self.rest);
+            if !line.trim().is_empty()
+            {
+                break;
+            }
+            self.rest = rest;
+        }
+
+        let start = self.rest;
+        let mut len = 0;
+        while !self.rest.is_empty()
+        {
+            let (line, rest) = split_line(self.rest);
+            if line.trim().is_empty()
+            {
+                break;
+            }
+            len += self.rest.len() - rest.len();
+            self.rest = rest;
+        }
+
+        Some(start[..len].trim_end_matches('\n'))
+    }
+}
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn normalizes_line_endings_bom_and_trailing_blanks()
+    {
+        assert_eq!(normalize("a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(normalize("\u{feff}a\nb"), "a\nb\n");
+        assert_eq!(normalize("a\n\n  \n\t\n"), "a\n");
+        assert_eq!(normalize("a\n \nb\n"), "a\n \nb\n");
+        assert_eq!(normalize(" \r\n\n"), "");
+    }
+
+    #[test]
+    fn splits_blocks_on_any_blank_run()
+    {
+        let input = "a\nb\n\n\n \nc\n\nd";
+        assert_eq!(blocks(input).collect::<Vec<&str>>(), vec!["a\nb", "c", "d"]);
+
+        let input = normalize("\u{feff}a\r\n\r\nb\r\n\r\n");
+        assert_eq!(blocks(&input).collect::<Vec<&str>>(), vec!["a", "b"]);
+        assert_eq!(blocks("").count(), 0);
+    }
+
+    #[test]
+    fn numbers_lines_of_slices()
+    {
+        let input = "a\n\nbc\nd";
+        let block = blocks(input).nth(1).unwrap();
+
+        assert_eq!(line_number(input, block), 3);
+        assert_eq!(line_number(input, &input[4..]), 3);
+        assert_eq!(line_number(input, "elsewhere"), 1);
+    }
+
+    #[test]
+    fn streams_lines_like_normalize()
+    {
+        let raw = "\u{feff}a\r\n\r\n \nb\n\n\t\n";
+        let lines = stream_lines(raw.as_bytes())
+            .collect::<io::Result<Vec<(usize, String)>>>()
+            .unwrap();
+
+        let expected = [(1, "a"), (2, ""), (3, ""), (4, "b")];
+        assert_eq!(lines, expected.map(|(n, line)| (n, line.to_string())));
+        assert_eq!(stream_lines("\n \n".as_bytes()).count(), 0);
+    }
+
+    #[test]
+    fn resolves_sources()
+    {
+        assert!(matches!(Source::resolve(Some("-"), 2023, 5), Source::Stdin));
+        assert_eq!(Source::resolve(Some("-"), 2023, 5).name(), "<stdin>");
+
+        match Source::resolve(None, 2023, 5) {
+            Source::Path(path) => assert_eq!(path, PathBuf::from("inputs").join("2023").join("5.txt")),
+            Source::Stdin => panic!("expected the default path"),
+        }
+        match Source::resolve(Some("my.txt"), 2023, 5) {
+            Source::Path(path) => assert_eq!(path, PathBuf::from("my.txt")),
+            Source::Stdin => panic!("expected a path"),
+        }
+    }
+}
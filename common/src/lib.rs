@@ -0,0 +1,11 @@
+pub mod command;
+pub mod error;
+pub mod golden;
+pub mod grid;
+pub mod input;
+pub mod solution;
+
+pub use command::Command;
+pub use error::{Error, ParseError};
+pub use grid::{Grid, Point};
+pub use solution::{Part, Report, Runner, Solution};
@@ -0,0 +1,81 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::Error;
+
+/// A day of the calendar: one parse shared by both parts.
+pub trait Solution
+{
+    type Input;
+    type Output: fmt::Display;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Output, Error>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Output, Error>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part
+{
+    One,
+    Two,
+}
+
+impl FromStr for Part
+{
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("invalid part `{s}`")),
+        }
+    }
+}
+
+impl fmt::Display for Part
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Report
+{
+    pub answer: String,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+/// Type-erased entry point so the runner can keep a table of days.
+pub type Runner = fn(&str, Part) -> Result<Report, Error>;
+
+pub fn run<S: Solution>(input: &str, part: Part) -> Result<Report, Error>
+{
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse_time = start.elapsed();
+
+    let start = Instant::now();
+    let answer = match part {
+        Part::One => S::part1(&parsed)?,
+        Part::Two => S::part2(&parsed)?,
+    };
+    let solve_time = start.elapsed();
+
+    Ok(Report {
+        answer: answer.to_string(),
+        parse_time,
+        solve_time,
+    })
+}